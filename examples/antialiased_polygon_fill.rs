@@ -0,0 +1,13 @@
+extern crate line_drawing;
+use line_drawing::{AntialiasedPolygonFill, FillRule};
+
+fn main() {
+    let vertices = [(0.0, 0.0), (12.5, 2.0), (6.0, 9.5)];
+    let mut buffer = [0.0; 16];
+
+    for (start, length, value) in
+        AntialiasedPolygonFill::<f32, i32>::new(&vertices, FillRule::NonZero, &mut buffer).spans()
+    {
+        println!("{:?} {} {:.2}", start, length, value);
+    }
+}
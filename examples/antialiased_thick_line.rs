@@ -0,0 +1,10 @@
+extern crate line_drawing;
+use line_drawing::{AntialiasedThickLine, LineCap};
+
+fn main() {
+    for ((x, y), value) in
+        AntialiasedThickLine::<f32, i8>::new((0.0, 0.0), (4.0, 2.5), 2.0, LineCap::Square)
+    {
+        print!("(({}, {}), {:.2}), ", x, y, value);
+    }
+}
@@ -0,0 +1,25 @@
+extern crate line_drawing;
+use line_drawing::{Arc, ArcDirection};
+
+fn main() {
+    let (start, end) = (0.0, std::f64::consts::PI * 0.75);
+
+    // A cooldown ring that's three-eighths of the way round
+    for (x, y) in Arc::new(0, 0, 6, start, end) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+
+    // The slice of a pie chart between two points, going the other way
+    for (x, y) in Arc::from_points(0, 0, 6, (5, -2), (-3, 4), ArcDirection::Clockwise) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+
+    // A chain code for the whole circle, starting at the top and going around in order
+    let top = std::f64::consts::FRAC_PI_2;
+    for (start, end) in Arc::whole_circle(0, 0, 4, top, ArcDirection::Clockwise).steps() {
+        print!("{:?} ", (end.0 - start.0, end.1 - start.1));
+    }
+    println!();
+}
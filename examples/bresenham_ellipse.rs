@@ -0,0 +1,15 @@
+extern crate line_drawing;
+use line_drawing::BresenhamEllipse;
+
+fn main() {
+    for (x, y) in BresenhamEllipse::new(0, 0, 8, 3) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+
+    // A hit box that's an even number of pixels across
+    for (x, y) in BresenhamEllipse::from_rect(((0, 0), (11, 5))) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+}
@@ -0,0 +1,18 @@
+extern crate line_drawing;
+use line_drawing::{AlphaTable, AntiAliased, GuptaSproull, Quantize, Transfer};
+
+fn main() {
+    // Encode the coverage as it goes
+    for ((x, y), value) in GuptaSproull::<f32, i8>::new((0.0, 0.0), (3.0, 6.0)).with_gamma(2.2) {
+        print!("(({}, {}), {}), ", x, y, u8::quantize(value));
+    }
+
+    println!();
+
+    // Or look it up in a table that's worked out ahead of time
+    let table = AlphaTable::<u8>::new(Transfer::Srgb);
+
+    for ((x, y), value) in GuptaSproull::<f32, i8>::new((0.0, 0.0), (3.0, 6.0)) {
+        print!("(({}, {}), {}), ", x, y, table.lookup(value));
+    }
+}
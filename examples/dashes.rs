@@ -0,0 +1,10 @@
+extern crate line_drawing;
+use line_drawing::{Bresenham, DashMeasure, DashPattern};
+
+fn main() {
+    let mut pattern = DashPattern::new(&[3.0, 2.0], DashMeasure::Length);
+
+    for (x, y) in pattern.dash(Bresenham::new((0, 0), (10, 6))) {
+        print!("({}, {}), ", x, y);
+    }
+}
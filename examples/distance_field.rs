@@ -0,0 +1,20 @@
+extern crate line_drawing;
+use line_drawing::{DistanceField, DistanceShape};
+
+fn main() {
+    // A triangle with a round hole in it
+    let shapes = [
+        DistanceShape::Segment((0.0, 0.0), (16.0, 0.0)),
+        DistanceShape::Segment((16.0, 0.0), (8.0, 12.0)),
+        DistanceShape::Segment((8.0, 12.0), (0.0, 0.0)),
+        DistanceShape::Circle((8.0, 4.0), 2.5),
+    ];
+
+    // Draw a glow that fades out over four pixels, clipped to a 20 by 20 image
+    let field = DistanceField::<f32, i32>::signed(&shapes, 4.0).clip(((0, 0), (19, 19)));
+
+    for (point, distance) in field {
+        let glow = (1.0 - distance.max(0.0) / 4.0).max(0.0);
+        println!("{:?} {:.2}", point, glow);
+    }
+}
@@ -0,0 +1,19 @@
+extern crate line_drawing;
+use line_drawing::{Annulus, BresenhamCircle, FilledCircle};
+
+fn main() {
+    // An area-of-effect marker, with its outline drawn on top of it
+    for (start, length) in FilledCircle::new(0, 0, 4) {
+        println!("{:?} {}", start, length);
+    }
+
+    for (x, y) in BresenhamCircle::new(0, 0, 4) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+
+    // A ring around it, which has two spans on the rows that go through the hole
+    for (start, length) in Annulus::new(0, 0, 5, 7) {
+        println!("{:?} {}", start, length);
+    }
+}
@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::GuptaSproull;
+
+fn main() {
+    for ((x, y), value) in GuptaSproull::<f32, i8>::new((0.0, 0.0), (3.0, 6.0)) {
+        print!("(({}, {}), {:.2}), ", x, y, value);
+    }
+}
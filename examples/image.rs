@@ -16,13 +16,13 @@ where
     }
 }
 
-// Draw an anti-aliased line of pixels
+// Draw an anti-aliased line of pixels, encoding the coverage for the sRGB image
 fn draw_xiaolin_wu(image: &mut Image, line: XiaolinWu<f32, i32>) {
-    for (point, value) in line {
+    for (point, value) in line.with_srgb() {
         image.put_pixel(
             point.0 as u32,
             point.1 as u32,
-            Rgb([(255.0 * value).round() as u8; 3]),
+            Rgb([u8::quantize(value); 3]),
         );
     }
 }
@@ -0,0 +1,20 @@
+extern crate line_drawing;
+use line_drawing::{FillRule, LineAlgorithm, Path, PathCommand};
+
+fn main() {
+    let mut buffer = [PathCommand::Close; 8];
+    let mut path = Path::new(&mut buffer);
+    path.move_to((1.0, 1.0))
+        .cubic_to((10.0, -2.0), (14.0, 12.0), (4.0, 10.0))
+        .line_to((1.0, 6.0))
+        .close();
+
+    for point in path.stroke::<i32>(0.25, &LineAlgorithm::Bresenham) {
+        println!("{:?}", point);
+    }
+
+    let mut row = [0.0; 16];
+    for (point, value) in path.fill::<i32>(0.25, FillRule::NonZero, &mut row) {
+        println!("{:?} {:.2}", point, value);
+    }
+}
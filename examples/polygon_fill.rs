@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::{FillRule, PolygonFill};
+
+fn main() {
+    for (start, length) in PolygonFill::new(&[(0, 0), (4, 0), (2, 4)], FillRule::NonZero) {
+        println!("{:?} {}", start, length);
+    }
+}
@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::{LineAlgorithm, Polyline};
+
+fn main() {
+    for (x, y) in Polyline::new(&[(0, 0), (3, 0), (3, 2)], &LineAlgorithm::Bresenham) {
+        print!("({}, {}), ", x, y);
+    }
+}
@@ -0,0 +1,18 @@
+extern crate line_drawing;
+use line_drawing::RotatedEllipse;
+
+fn main() {
+    let angle = std::f64::consts::PI / 6.0;
+
+    for (x, y) in RotatedEllipse::<f64, i32>::new((0.0, 0.0), 8.0, 3.0, angle) {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+
+    // A sheared circle, from the middles of the sides of a parallelogram
+    for (x, y) in RotatedEllipse::<f64, i32>::from_parallelogram((0.0, 0.0), (6.0, 0.0), (3.0, 5.0))
+    {
+        print!("({}, {}), ", x, y);
+    }
+    println!();
+}
@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::Bresenham;
+
+fn main() {
+    for (start, length, axis) in Bresenham::new((0, 0), (7, 2)).spans() {
+        println!("{:?} {} {:?}", start, length, axis);
+    }
+}
@@ -0,0 +1,17 @@
+extern crate line_drawing;
+use line_drawing::{StrokeCap, StrokeJoin, StrokeOutline};
+
+fn main() {
+    let route = [(2.0, 2.0), (14.0, 4.0), (8.0, 12.0), (20.0, 16.0)];
+    let outline = StrokeOutline::new(&route, 3.0, StrokeCap::Round, StrokeJoin::Miter(4.0), 0.1);
+
+    let mut buffer = [0.0; 32];
+    for (start, length, value) in outline.clone().fill_antialiased::<i32>(&mut buffer).spans() {
+        println!("{:?} {} {:.2}", start, length, value);
+    }
+
+    let mut buffer = [(0, 0); 64];
+    for (start, length) in outline.fill::<i32>(&mut buffer) {
+        println!("{:?} {}", start, length);
+    }
+}
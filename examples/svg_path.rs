@@ -0,0 +1,21 @@
+extern crate line_drawing;
+use line_drawing::{FillRule, Path, PathCommand};
+
+fn main() {
+    // A heart, scaled up to 32 pixels across
+    let data = "M16 29.5 4.2 17.6a7 7 0 0 1 9.9-9.9L16 9.6l1.9-1.9a7 7 0 0 1 9.9 9.9z";
+
+    let mut buffer = [PathCommand::Close; 16];
+    let path = match Path::<f32>::from_svg(data, &mut buffer) {
+        Ok(path) => path,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+
+    let mut row = [0.0; 32];
+    for (point, value) in path.fill::<i32>(0.25, FillRule::NonZero, &mut row) {
+        println!("{:?} {:.2}", point, value);
+    }
+}
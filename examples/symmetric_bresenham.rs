@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::SymmetricBresenham;
+
+fn main() {
+    for (x, y) in SymmetricBresenham::new((0, 0), (5, 6)) {
+        print!("({}, {}), ", x, y);
+    }
+}
@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::{ThickLine, WidthMeasure};
+
+fn main() {
+    for (x, y) in ThickLine::new((0, 0), (5, 6), 3, &WidthMeasure::Perpendicular) {
+        print!("({}, {}), ", x, y);
+    }
+}
@@ -0,0 +1,8 @@
+extern crate line_drawing;
+use line_drawing::XiaolinWuCircle;
+
+fn main() {
+    for ((x, y), value) in XiaolinWuCircle::<f32, i8>::new(0.5, 0.0, 4.2) {
+        print!("(({}, {}), {:.2}), ", x, y, value);
+    }
+}
@@ -0,0 +1,401 @@
+use {FillRule, FloatNum, Point, SignedNum};
+use core::mem::take;
+
+/// A shape made out of straight edges, which can be filled by an [`AntialiasedPolygonFill`].
+///
+/// This is implemented for slices of vertices, where the last vertex is joined back to the first,
+/// for flattened [`Path`]s and for [`StrokeOutline`]s.
+///
+/// [`AntialiasedPolygonFill`]: struct.AntialiasedPolygonFill.html
+/// [`Path`]: struct.Path.html
+/// [`StrokeOutline`]: struct.StrokeOutline.html
+pub trait Edges<I> {
+    /// Call `edge` with the start and end of each edge. The edges should join up into closed
+    /// loops, but they can be given in any order.
+    fn edges<F: FnMut(Point<I>, Point<I>)>(&self, edge: F);
+}
+
+impl<I: FloatNum> Edges<I> for &[Point<I>] {
+    #[inline]
+    fn edges<F: FnMut(Point<I>, Point<I>)>(&self, mut edge: F) {
+        for (&a, &b) in self.iter().zip(self.iter().cycle().skip(1)) {
+            edge(a, b);
+        }
+    }
+}
+
+/// An anti-aliased filled polygon, using a signed area accumulation buffer like most font
+/// rasterizers.
+///
+/// The vertices are floating-point positions where the integers are the centres of pixels, and
+/// the last vertex is joined back to the first. Other shapes can be filled with [`with_edges`].
+/// Each edge adds the area it covers to the cells of a row buffer, signed by which way it goes,
+/// and adding up the buffer from left to right gives how many times the polygon winds around each
+/// pixel. The [`FillRule`] then turns that into coverage from zero to one.
+///
+/// No memory is allocated, so a buffer to add the edges into has to be given. It can be any
+/// length other than zero, but rows that are wider than it are worked out a piece at a time, with
+/// every edge being added for each piece. A buffer at least as wide as the polygon is fastest.
+///
+/// The pixels are returned a row at a time in order of increasing y, and from left to right
+/// within each row. Pixels with no coverage are skipped, and [`spans`] joins up runs of pixels
+/// with the same coverage, so the inside of a large shape is returned as one span per row.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{AntialiasedPolygonFill, FillRule};
+///
+/// fn main() {
+///     let vertices = [(0.0, 0.0), (3.0, 0.0), (0.0, 3.0)];
+///     let mut buffer = [0.0; 8];
+///     let fill = AntialiasedPolygonFill::<f32, i8>::new(&vertices, FillRule::NonZero, &mut buffer);
+///
+///     for ((x, y), value) in fill {
+///         print!("(({}, {}), {:.3}), ", x, y, value);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 0.250), ((1, 0), 0.500), ((2, 0), 0.500), ((3, 0), 0.125), ((0, 1), 0.500), ((1, 1), 1.000), ((2, 1), 0.500), ((0, 2), 0.500), ((1, 2), 0.500), ((0, 3), 0.125),
+/// ```
+///
+/// [`FillRule`]: enum.FillRule.html
+/// [`with_edges`]: #method.with_edges
+/// [`spans`]: #method.spans
+pub struct AntialiasedPolygonFill<'a, I: 'a, O, E = &'a [Point<I>]> {
+    shape: E,
+    rule: FillRule,
+    buffer: &'a mut [I],
+    start_x: O,
+    end_x: O,
+    y: O,
+    end_y: O,
+    chunk: O,
+    index: usize,
+    length: usize,
+    winding: I,
+}
+
+impl<'a, I: FloatNum, O: SignedNum> AntialiasedPolygonFill<'a, I, O> {
+    /// Fill the polygon through `vertices`, using `buffer` to add up each row.
+    ///
+    /// # Panics
+    ///
+    /// If `buffer` is empty.
+    #[inline]
+    pub fn new(vertices: &'a [Point<I>], rule: FillRule, buffer: &'a mut [I]) -> Self {
+        Self::with_edges(vertices, rule, buffer)
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum, E: Edges<I>> AntialiasedPolygonFill<'a, I, O, E> {
+    /// Fill any shape made of edges, using `buffer` to add up each row.
+    ///
+    /// # Panics
+    ///
+    /// If `buffer` is empty.
+    #[inline]
+    pub fn with_edges(shape: E, rule: FillRule, buffer: &'a mut [I]) -> Self {
+        assert!(!buffer.is_empty(), "the buffer must not be empty");
+
+        let half = I::cast(0.5);
+        let mut low = (I::infinity(), I::infinity());
+        let mut high = (I::neg_infinity(), I::neg_infinity());
+
+        shape.edges(|a, b| {
+            low = (low.0.min(a.0).min(b.0), low.1.min(a.1).min(b.1));
+            high = (high.0.max(a.0).max(b.0), high.1.max(a.1).max(b.1));
+        });
+
+        let (start_x, end_x, y, end_y) = if low.0 > high.0 {
+            (O::zero(), O::zero(), O::one(), O::zero())
+        } else {
+            (
+                O::cast((low.0 + half).floor()),
+                O::cast((high.0 + half).floor()),
+                O::cast((low.1 + half).floor()),
+                O::cast((high.1 + half).ceil()) - O::one(),
+            )
+        };
+
+        let mut fill = Self {
+            shape,
+            rule,
+            buffer,
+            start_x,
+            end_x,
+            y,
+            end_y,
+            chunk: start_x,
+            index: 0,
+            length: 0,
+            winding: I::zero(),
+        };
+
+        fill.start_chunk();
+        fill
+    }
+
+    /// Join up runs of pixels in the same row with the same coverage, returning
+    /// `(start, length, coverage)` for each.
+    #[inline]
+    pub fn spans(self) -> AntialiasedSpans<'a, I, O, E> {
+        AntialiasedSpans {
+            fill: self,
+            next: None,
+        }
+    }
+
+    /// Add up the edges for the piece of the current row starting at `self.chunk`.
+    #[inline]
+    fn start_chunk(&mut self) {
+        if self.y > self.end_y {
+            return;
+        }
+
+        let remaining = (self.end_x - self.chunk + O::one()).to_usize().unwrap();
+        self.length = remaining.min(self.buffer.len());
+        self.index = 0;
+
+        let buffer = take(&mut self.buffer);
+        let cells = &mut buffer[..self.length];
+
+        for cell in cells.iter_mut() {
+            *cell = I::zero();
+        }
+
+        // Everything to the left of the buffer is added straight onto the winding
+        let mut carry = I::zero();
+
+        {
+            let fill = &*self;
+            fill.shape
+                .edges(|a, b| fill.add_edge(cells, a, b, &mut carry));
+        }
+
+        self.buffer = buffer;
+        self.winding = carry;
+    }
+
+    /// Add the area that the edge from `a` to `b` covers in the current row, and how far up the
+    /// row it goes, to the cells that it passes through.
+    #[inline]
+    fn add_edge(&self, cells: &mut [I], a: Point<I>, b: Point<I>, carry: &mut I) {
+        let half = I::cast(0.5);
+        let one = I::one();
+
+        // Move the edges so that each cell covers `x..x + 1` and `y..y + 1`
+        let (a, b) = ((a.0 + half, a.1 + half), (b.0 + half, b.1 + half));
+        let (direction, a, b) = if a.1 < b.1 { (one, a, b) } else { (-one, b, a) };
+
+        let top = I::cast(self.y);
+        let y0 = a.1.max(top);
+        let y1 = b.1.min(top + one);
+
+        if y1 <= y0 {
+            return;
+        }
+
+        let gradient = (b.0 - a.0) / (b.1 - a.1);
+        let x = a.0 + (y0 - a.1) * gradient;
+        let next_x = a.0 + (y1 - a.1) * gradient;
+        let height = (y1 - y0) * direction;
+
+        let (x0, x1) = if x < next_x { (x, next_x) } else { (next_x, x) };
+        let x0_floor = x0.floor();
+        let x1_ceil = x1.ceil();
+        let first = O::cast(x0_floor);
+        let last = O::cast(x1_ceil);
+
+        let mut add = |cell: O, value: I| {
+            if cell < self.chunk {
+                *carry += value;
+            } else {
+                let index = (cell - self.chunk).to_usize().unwrap();
+
+                if index < cells.len() {
+                    cells[index] += value;
+                }
+            }
+        };
+
+        if last <= first + O::one() {
+            // The edge stays within one cell
+            let middle = half * (x + next_x) - x0_floor;
+            add(first, height * (one - middle));
+            add(first + O::one(), height * middle);
+        } else {
+            let step = (x1 - x0).recip();
+            let x0_fract = x0 - x0_floor;
+            let x1_fract = x1 - x1_ceil + one;
+            let first_area = half * step * (one - x0_fract) * (one - x0_fract);
+            let last_area = half * step * x1_fract * x1_fract;
+
+            add(first, height * first_area);
+
+            if last == first + O::cast(2) {
+                add(first + O::one(), height * (one - first_area - last_area));
+            } else {
+                let second_area = step * (I::cast(1.5) - x0_fract);
+                add(first + O::one(), height * (second_area - first_area));
+
+                let mut cell = first + O::cast(2);
+                while cell < last - O::one() {
+                    add(cell, height * step);
+                    cell += O::one();
+                }
+
+                let area = second_area + I::cast(last - first - O::cast(3)) * step;
+                add(last - O::one(), height * (one - area - last_area));
+            }
+
+            add(last, height * last_area);
+        }
+    }
+
+    /// Turn the number of times the polygon winds around a pixel into its coverage.
+    ///
+    /// The winding is rounded to the nearest 1/65536, which is as precise as a `u16` and hides
+    /// the rounding errors from adding up the buffer, so empty pixels don't end up with a tiny
+    /// bit of coverage.
+    #[inline]
+    fn coverage(&self, winding: I) -> I {
+        let steps = I::cast(65_536);
+        let winding = (winding.abs() * steps).round() / steps;
+
+        match self.rule {
+            FillRule::NonZero => winding.min(I::one()),
+            FillRule::EvenOdd => {
+                let two = I::cast(2);
+                let winding = winding % two;
+
+                if winding > I::one() {
+                    two - winding
+                } else {
+                    winding
+                }
+            }
+        }
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum, E: Edges<I>> Iterator for AntialiasedPolygonFill<'a, I, O, E> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.y <= self.end_y {
+            while self.index < self.length {
+                let x = self.chunk + O::cast(self.index);
+                self.winding += self.buffer[self.index];
+                self.index += 1;
+
+                let coverage = self.coverage(self.winding);
+
+                if coverage > I::zero() {
+                    return Some(((x, self.y), coverage));
+                }
+            }
+
+            // Move on to the next piece of the row, or the next row
+            self.chunk += O::cast(self.length);
+
+            if self.chunk > self.end_x {
+                self.chunk = self.start_x;
+                self.y += O::one();
+            }
+
+            self.start_chunk();
+        }
+
+        None
+    }
+}
+
+/// Runs of pixels with the same coverage from an [`AntialiasedPolygonFill`].
+///
+/// Each span covers `start.0..start.0 + length` on row `start.1`.
+///
+/// [`AntialiasedPolygonFill`]: struct.AntialiasedPolygonFill.html
+pub struct AntialiasedSpans<'a, I: 'a, O, E = &'a [Point<I>]> {
+    fill: AntialiasedPolygonFill<'a, I, O, E>,
+    next: Option<(Point<O>, I)>,
+}
+
+impl<'a, I: FloatNum, O: SignedNum, E: Edges<I>> Iterator for AntialiasedSpans<'a, I, O, E> {
+    type Item = (Point<O>, usize, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, coverage) = match self.next.take() {
+            Some(item) => item,
+            None => self.fill.next()?,
+        };
+
+        let mut length = 1;
+
+        for (point, value) in &mut self.fill {
+            if point != (start.0 + O::cast(length), start.1) || value != coverage {
+                self.next = Some((point, value));
+                break;
+            }
+
+            length += 1;
+        }
+
+        Some((start, length, coverage))
+    }
+}
+
+#[test]
+fn tests() {
+    let fill = |vertices: &[Point<f64>], rule| {
+        let mut buffer = [0.0; 16];
+        AntialiasedPolygonFill::<_, i32>::new(vertices, rule, &mut buffer)
+            .spans()
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        fill(
+            &[(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0)],
+            FillRule::NonZero
+        ),
+        [
+            ((0, 0), 1, 0.25),
+            ((1, 0), 2, 0.5),
+            ((3, 0), 1, 0.25),
+            ((0, 1), 1, 0.5),
+            ((1, 1), 2, 1.0),
+            ((3, 1), 1, 0.5),
+            ((0, 2), 1, 0.5),
+            ((1, 2), 2, 1.0),
+            ((3, 2), 1, 0.5),
+            ((0, 3), 1, 0.25),
+            ((1, 3), 2, 0.5),
+            ((3, 3), 1, 0.25)
+        ]
+    );
+
+    // Two squares on top of each other cancel out with the even-odd rule
+    let square = [
+        (-0.5, -0.5),
+        (1.5, -0.5),
+        (1.5, 1.5),
+        (-0.5, 1.5),
+        (-0.5, -0.5),
+        (1.5, -0.5),
+        (1.5, 1.5),
+        (-0.5, 1.5),
+    ];
+    assert_eq!(
+        fill(&square, FillRule::NonZero),
+        [((0, 0), 2, 1.0), ((0, 1), 2, 1.0)]
+    );
+    assert_eq!(fill(&square, FillRule::EvenOdd), []);
+
+    assert_eq!(fill(&[], FillRule::NonZero), []);
+}
@@ -0,0 +1,276 @@
+use {FloatNum, Point, SignedNum};
+
+/// The shape of the ends of an [`AntialiasedThickLine`].
+///
+/// [`AntialiasedThickLine`]: struct.AntialiasedThickLine.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    /// The line stops exactly at its start and end.
+    Butt,
+    /// The line carries on past its start and end by half of its width.
+    Square,
+}
+
+/// A polygon with up to eight corners, which is enough for a rectangle clipped to a square.
+type Polygon<I> = ([Point<I>; 8], usize);
+
+/// An anti-aliased line with a configurable width, using the exact area of each pixel it covers.
+///
+/// The line is a rectangle `width` wide around the segment from `start` to `end`, with the ends
+/// shaped by a [`LineCap`]. Pixels are squares around their centres, and each pixel the rectangle
+/// touches is returned once along with how much of its area is covered, from zero to one. This
+/// means the total coverage is always the area of the rectangle.
+///
+/// The pixels are returned a row at a time in order of increasing y, and from left to right
+/// within each row.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{AntialiasedThickLine, LineCap};
+///
+/// fn main() {
+///     let line = AntialiasedThickLine::<f32, i8>::new((0.0, 0.0), (3.0, 1.0), 1.5, LineCap::Butt);
+///
+///     for ((x, y), value) in line {
+///         print!("(({}, {}), {:.2}), ", x, y, value);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, -1), 0.05), ((1, -1), 0.02), ((0, 0), 0.50), ((1, 0), 0.93), ((2, 0), 0.62), ((3, 0), 0.24), ((0, 1), 0.24), ((1, 1), 0.62), ((2, 1), 0.93), ((3, 1), 0.50), ((2, 2), 0.02), ((3, 2), 0.05),
+/// ```
+///
+/// [`LineCap`]: enum.LineCap.html
+pub struct AntialiasedThickLine<I, O> {
+    corners: [Point<I>; 4],
+    row: Polygon<I>,
+    x: O,
+    end_x: O,
+    y: O,
+    end_y: O,
+}
+
+impl<I: FloatNum, O: SignedNum> AntialiasedThickLine<I, O> {
+    #[inline]
+    pub fn new(start: Point<I>, end: Point<I>, width: I, cap: LineCap) -> Self {
+        let half = I::cast(0.5);
+        let delta = (end.0 - start.0, end.1 - start.1);
+        let length = delta.0.hypot(delta.1);
+
+        // A single point is drawn as if it were horizontal
+        let along = if length.is_zero() {
+            (I::one(), I::zero())
+        } else {
+            (delta.0 / length, delta.1 / length)
+        };
+
+        let extend = match cap {
+            LineCap::Butt => I::zero(),
+            LineCap::Square => width * half,
+        };
+
+        let along = (along.0 * extend, along.1 * extend);
+        let across = (-delta.1, delta.0);
+        let across = if length.is_zero() {
+            (I::zero(), width * half)
+        } else {
+            (
+                across.0 / length * width * half,
+                across.1 / length * width * half,
+            )
+        };
+
+        let start = (start.0 - along.0, start.1 - along.1);
+        let end = (end.0 + along.0, end.1 + along.1);
+
+        let corners = [
+            (start.0 + across.0, start.1 + across.1),
+            (end.0 + across.0, end.1 + across.1),
+            (end.0 - across.0, end.1 - across.1),
+            (start.0 - across.0, start.1 - across.1),
+        ];
+
+        let (low, high) = bounds(&corners, |point| point.1);
+
+        let mut line = Self {
+            corners,
+            row: ([(I::zero(), I::zero()); 8], 0),
+            x: O::one(),
+            end_x: O::zero(),
+            y: O::cast((low + half).floor()),
+            end_y: O::cast((high - half).ceil()),
+        };
+
+        line.start_row();
+        line
+    }
+
+    /// Clip the line to the current row, and find the pixels it touches there.
+    #[inline]
+    fn start_row(&mut self) {
+        let half = I::cast(0.5);
+        let y = I::cast(self.y);
+
+        let mut row = ([(I::zero(), I::zero()); 8], 4);
+        row.0[..4].copy_from_slice(&self.corners);
+        let row = clip(&row, |point| point.1, y - half, false);
+        let row = clip(&row, |point| point.1, y + half, true);
+
+        if row.1 > 0 {
+            let (low, high) = bounds(&row.0[..row.1], |point| point.0);
+            self.x = O::cast((low + half).floor());
+            self.end_x = O::cast((high - half).ceil());
+        } else {
+            self.x = O::one();
+            self.end_x = O::zero();
+        }
+
+        self.row = row;
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for AntialiasedThickLine<I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let half = I::cast(0.5);
+
+        while self.y <= self.end_y {
+            while self.x <= self.end_x {
+                let x = self.x;
+                self.x += O::one();
+
+                let pixel = clip(&self.row, |point| point.0, I::cast(x) - half, false);
+                let pixel = clip(&pixel, |point| point.0, I::cast(x) + half, true);
+                let coverage = area(&pixel.0[..pixel.1]).min(I::one());
+
+                if coverage > I::zero() {
+                    return Some(((x, self.y), coverage));
+                }
+            }
+
+            self.y += O::one();
+            self.start_row();
+        }
+
+        None
+    }
+}
+
+/// Get the lowest and highest value of `axis` over `points`.
+#[inline]
+fn bounds<I: FloatNum, A: Fn(&Point<I>) -> I>(points: &[Point<I>], axis: A) -> (I, I) {
+    points
+        .iter()
+        .fold((I::infinity(), I::neg_infinity()), |(low, high), point| {
+            (low.min(axis(point)), high.max(axis(point)))
+        })
+}
+
+/// Cut off the part of a convex polygon on one side of a line where `axis` equals `bound`,
+/// keeping the part below it if `below` is true, and above it otherwise.
+#[inline]
+fn clip<I, A>(polygon: &Polygon<I>, axis: A, bound: I, below: bool) -> Polygon<I>
+where
+    I: FloatNum,
+    A: Fn(&Point<I>) -> I,
+{
+    let (points, count) = *polygon;
+    let mut clipped = ([(I::zero(), I::zero()); 8], 0);
+
+    let inside = |point: &Point<I>| (axis(point) <= bound) == below || axis(point) == bound;
+
+    for (i, &point) in points[..count].iter().enumerate() {
+        let next = points[(i + 1) % count];
+
+        if inside(&point) {
+            clipped.0[clipped.1] = point;
+            clipped.1 += 1;
+        }
+
+        // Add the point where the edge crosses the line
+        if inside(&point) != inside(&next) {
+            let t = (bound - axis(&point)) / (axis(&next) - axis(&point));
+            clipped.0[clipped.1] = (
+                point.0 + (next.0 - point.0) * t,
+                point.1 + (next.1 - point.1) * t,
+            );
+            clipped.1 += 1;
+        }
+    }
+
+    clipped
+}
+
+/// Get the area of a polygon, whichever way around it goes.
+#[inline]
+fn area<I: FloatNum>(points: &[Point<I>]) -> I {
+    let twice = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .fold(I::zero(), |total, (a, b)| total + a.0 * b.1 - b.0 * a.1);
+
+    (twice * I::cast(0.5)).abs()
+}
+
+#[test]
+fn tests() {
+    let line = |a, b, width, cap| {
+        AntialiasedThickLine::<f64, i32>::new(a, b, width, cap).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        line((0.0, 0.0), (4.0, 0.0), 1.0, LineCap::Butt),
+        [
+            ((0, 0), 0.5),
+            ((1, 0), 1.0),
+            ((2, 0), 1.0),
+            ((3, 0), 1.0),
+            ((4, 0), 0.5)
+        ]
+    );
+
+    assert_eq!(
+        line((0.0, 0.0), (2.0, 0.0), 2.0, LineCap::Square),
+        [
+            ((-1, -1), 0.25),
+            ((0, -1), 0.5),
+            ((1, -1), 0.5),
+            ((2, -1), 0.5),
+            ((3, -1), 0.25),
+            ((-1, 0), 0.5),
+            ((0, 0), 1.0),
+            ((1, 0), 1.0),
+            ((2, 0), 1.0),
+            ((3, 0), 0.5),
+            ((-1, 1), 0.25),
+            ((0, 1), 0.5),
+            ((1, 1), 0.5),
+            ((2, 1), 0.5),
+            ((3, 1), 0.25)
+        ]
+    );
+
+    // A single point with square caps is a square
+    assert_eq!(
+        line((0.5, 0.5), (0.5, 0.5), 1.0, LineCap::Square),
+        [
+            ((0, 0), 0.25),
+            ((1, 0), 0.25),
+            ((0, 1), 0.25),
+            ((1, 1), 0.25)
+        ]
+    );
+    assert_eq!(line((0.5, 0.5), (0.5, 0.5), 1.0, LineCap::Butt), []);
+
+    // A diagonal line covers its area exactly
+    let total: f64 = line((0.2, 0.1), (5.3, 3.7), 2.5, LineCap::Butt)
+        .iter()
+        .map(|&(_, value)| value)
+        .sum();
+    assert!((total - 2.5 * (5.1f64).hypot(3.6)).abs() < 1e-9);
+}
@@ -0,0 +1,354 @@
+use {FloatNum, Point, SignedNum};
+use core::f64::consts::{FRAC_PI_2, PI};
+use steps::Steps;
+
+/// Which way an [`Arc`] goes around its circle.
+///
+/// Counterclockwise is the way that angles increase, turning from the x axis towards the y axis.
+/// This looks counterclockwise when y points up, and clockwise on a screen where y points down.
+///
+/// [`Arc`]: struct.Arc.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcDirection {
+    /// Go around in the direction of increasing angles.
+    Counterclockwise,
+    /// Go around in the direction of decreasing angles.
+    Clockwise,
+}
+
+/// Part of a circle, with the same points as [`BresenhamCircle`] but in order around the circle.
+///
+/// The arc goes from a start angle to an end angle, and includes the points that lie at either of
+/// them. Angles are in radians, turning from the x axis towards the y axis, and the arc goes
+/// counterclockwise if the end angle is larger than the start angle, or clockwise if it's smaller.
+/// An arc of a whole turn or more is the whole circle, starting at the start angle, and each point
+/// is only returned once, which can also be made with [`whole_circle`]. An arc can also be given by
+/// points that lie in the direction of its start and end from the centre, with [`from_points`].
+///
+/// Each quarter of the circle is walked with the same steps as [`BresenhamCircle`], one after
+/// another. The walk starts at the beginning of the quarter that holds the start angle, and the
+/// points before the start are stepped over without being returned, so as well as the arc itself,
+/// up to a quarter of the circle is walked and skipped.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Arc;
+///
+/// fn main() {
+///     let quarter = std::f32::consts::FRAC_PI_2;
+///
+///     for (x, y) in Arc::new(0, 0, 3, 0.0, quarter) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (3, 0), (3, 1), (2, 2), (1, 3), (0, 3),
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+/// [`whole_circle`]: #method.whole_circle
+/// [`from_points`]: #method.from_points
+pub struct Arc<T> {
+    center: Point<T>,
+    radius: T,
+    x: T,
+    y: T,
+    error: T,
+    quadrant: u8,
+    turns: u8,
+    start: Option<Point<f64>>,
+    end: Point<f64>,
+    whole: bool,
+    clockwise: bool,
+}
+
+impl<T: SignedNum> Arc<T> {
+    #[inline]
+    pub fn new<I: FloatNum>(center_x: T, center_y: T, radius: T, start: I, end: I) -> Self {
+        let clockwise = end < start;
+        let (start, end) = if clockwise {
+            (-start, -end)
+        } else {
+            (start, end)
+        };
+
+        let sweep = (end - start).min(I::cast(2.0 * PI));
+        let start = wrap(start);
+        let (end_quadrant, end) = locate(start + sweep);
+        let (start_quadrant, start) = locate(start);
+        let arc = Self::circle(center_x, center_y, radius, start_quadrant, start, clockwise);
+
+        if sweep == I::cast(2.0 * PI) {
+            arc
+        } else {
+            Self {
+                turns: end_quadrant - start_quadrant,
+                end,
+                whole: false,
+                ..arc
+            }
+        }
+    }
+
+    /// Make an arc that goes all the way around the circle, starting at the `start` angle. This
+    /// returns the same points as [`BresenhamCircle`] as one 8-connected loop, where each point is
+    /// next to the one before it, and each point is only returned once.
+    ///
+    /// [`BresenhamCircle`]: struct.BresenhamCircle.html
+    #[inline]
+    pub fn whole_circle<I: FloatNum>(
+        center_x: T,
+        center_y: T,
+        radius: T,
+        start: I,
+        direction: ArcDirection,
+    ) -> Self {
+        let clockwise = direction == ArcDirection::Clockwise;
+        let (quadrant, start) = locate(wrap(if clockwise { -start } else { start }));
+
+        Self::circle(center_x, center_y, radius, quadrant, start, clockwise)
+    }
+
+    /// Make an arc that starts in the direction of `start` from the centre, and ends in the
+    /// direction of `end`. The points don't need to be on the circle. If they're in the same
+    /// direction, the arc is the whole circle, and a point right on the centre counts as being at
+    /// an angle of zero.
+    #[inline]
+    pub fn from_points(
+        center_x: T,
+        center_y: T,
+        radius: T,
+        start: Point<T>,
+        end: Point<T>,
+        direction: ArcDirection,
+    ) -> Self {
+        let clockwise = direction == ArcDirection::Clockwise;
+
+        // Turn each point back into the first quarter of the circle
+        let locate = |point: Point<T>| {
+            let x = (point.0 - center_x).to_f64().unwrap();
+            let y = (point.1 - center_y).to_f64().unwrap();
+            let y = if clockwise { -y } else { y };
+
+            if x == 0.0 && y == 0.0 {
+                (0, (1.0, 0.0))
+            } else if y >= 0.0 && x > 0.0 {
+                (0, (x, y))
+            } else if x <= 0.0 && y > 0.0 {
+                (1, (y, -x))
+            } else if y <= 0.0 && x < 0.0 {
+                (2, (-x, -y))
+            } else {
+                (3, (-y, x))
+            }
+        };
+
+        let (start_quadrant, start) = locate(start);
+        let (end_quadrant, end) = locate(end);
+        let turns = (end_quadrant + 4 - start_quadrant) % 4;
+        let cross = turned(start, end);
+        let arc = Self::circle(center_x, center_y, radius, start_quadrant, start, clockwise);
+
+        // Going all the way around when the end is behind the start in the same quarter
+        if turns == 0 && cross <= 0.0 {
+            Self {
+                end,
+                whole: cross == 0.0,
+                ..arc
+            }
+        } else {
+            Self {
+                turns,
+                end,
+                whole: false,
+                ..arc
+            }
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        Steps::new(self)
+    }
+
+    /// Start walking the whole circle from `start`, in the quarter given by `quadrant`.
+    #[inline]
+    fn circle(
+        center_x: T,
+        center_y: T,
+        radius: T,
+        quadrant: u8,
+        start: Point<f64>,
+        clockwise: bool,
+    ) -> Self {
+        Self {
+            center: (center_x, center_y),
+            radius,
+            x: -radius,
+            y: T::zero(),
+            error: T::cast(2) - T::cast(2) * radius,
+            quadrant: quadrant % 4,
+            turns: 4,
+            start: Some(start),
+            end: start,
+            whole: true,
+            clockwise,
+        }
+    }
+}
+
+/// Wrap an angle around into the first turn of the circle.
+#[inline]
+fn wrap<I: FloatNum>(angle: I) -> I {
+    let turn = I::cast(2.0 * PI);
+    angle - (angle / turn).floor() * turn
+}
+
+/// Find the quarter of the circle that an angle is in, and the direction of the angle from the
+/// start of that quarter.
+#[inline]
+fn locate<I: FloatNum>(angle: I) -> (u8, Point<f64>) {
+    let quarter = I::cast(FRAC_PI_2);
+    let quadrant = (angle / quarter).floor().to_u8().unwrap_or(0);
+    let (sin, cos) = (angle - quarter * I::cast(quadrant)).sin_cos();
+
+    (quadrant, (cos.to_f64().unwrap(), sin.to_f64().unwrap()))
+}
+
+/// Check how far `point` has turned past `direction`, as the sign of their cross product.
+#[inline]
+fn turned(direction: Point<f64>, point: Point<f64>) -> f64 {
+    direction.0 * point.1 - direction.1 * point.0
+}
+
+impl<T: SignedNum> Iterator for Arc<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Move on to the next quarter of the circle
+            if self.x >= T::zero() {
+                if self.turns == 0 || self.radius <= T::zero() {
+                    return None;
+                }
+
+                self.turns -= 1;
+                self.quadrant = (self.quadrant + 1) % 4;
+                self.start = None;
+                self.x = -self.radius;
+                self.y = T::zero();
+                self.error = T::cast(2) - T::cast(2) * self.radius;
+            }
+
+            let (u, v) = (-self.x, self.y);
+            let local = (u.to_f64().unwrap(), v.to_f64().unwrap());
+
+            let error = self.error;
+
+            if error <= self.y {
+                self.y += T::one();
+                self.error += self.y * T::cast(2) + T::one();
+            }
+
+            if error > self.x || self.error > self.y {
+                self.x += T::one();
+                self.error += self.x * T::cast(2) + T::one();
+            }
+
+            if let Some(start) = self.start {
+                if turned(start, local) < 0.0 {
+                    continue;
+                }
+
+                self.start = None;
+            }
+
+            if self.turns == 0 {
+                let past = turned(self.end, local);
+
+                if past > 0.0 || self.whole && past >= 0.0 {
+                    self.x = T::zero();
+                    return None;
+                }
+            }
+
+            let (x, y) = match self.quadrant {
+                0 => (u, v),
+                1 => (-v, u),
+                2 => (-u, -v),
+                _ => (v, -u),
+            };
+
+            let y = if self.clockwise { -y } else { y };
+
+            return Some((self.center.0 + x, self.center.1 + y));
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use std::f64::consts::PI;
+    use BresenhamCircle;
+
+    let arc = |start, end| Arc::new(0, 0, 2, start, end).collect::<Vec<_>>();
+
+    // A whole turn is the same as the circle, and each point is only returned once
+    let mut whole = arc(0.0, 2.0 * PI);
+    assert_eq!(
+        whole,
+        [
+            (2, 0),
+            (2, 1),
+            (1, 2),
+            (0, 2),
+            (-1, 2),
+            (-2, 1),
+            (-2, 0),
+            (-2, -1),
+            (-1, -2),
+            (0, -2),
+            (1, -2),
+            (2, -1)
+        ]
+    );
+
+    let mut circle = BresenhamCircle::new(0, 0, 2).collect::<Vec<_>>();
+    whole.sort();
+    circle.sort();
+    assert_eq!(whole, circle);
+
+    // Going the other way gives the same points in reverse
+    let mut reversed = arc(PI / 2.0, 0.0);
+    reversed.reverse();
+    assert_eq!(reversed, arc(0.0, PI / 2.0));
+
+    // The points don't need to be on the circle
+    assert_eq!(
+        Arc::from_points(0, 0, 2, (1, 1), (-5, 5), ArcDirection::Counterclockwise)
+            .collect::<Vec<_>>(),
+        [(1, 2), (0, 2), (-1, 2)]
+    );
+
+    assert_eq!(
+        Arc::from_points(0, 0, 2, (1, 1), (-5, 5), ArcDirection::Clockwise).count(),
+        9
+    );
+
+    // A whole circle can start anywhere around it
+    let mut turned = Arc::whole_circle(0, 0, 2, PI, ArcDirection::Counterclockwise);
+    assert_eq!(turned.next(), Some((-2, 0)));
+    assert_eq!(turned.count(), 11);
+
+    let mut backwards =
+        Arc::whole_circle(0, 0, 2, 0.0, ArcDirection::Clockwise).collect::<Vec<_>>();
+    backwards[1..].reverse();
+    assert_eq!(backwards, arc(0.0, 2.0 * PI));
+
+    assert_eq!(Arc::new(0, 0, 0, 0.0, 2.0 * PI).count(), 0);
+}
@@ -1,10 +1,14 @@
-use {Point, SignedNum};
+use {wide, Point, Rect, SignedNum};
+use core::cmp::{max, min};
+use num_traits::NumCast;
 use octant::Octant;
+use spans::{Axis, Spans};
 use steps::Steps;
 
 /// An implementation of [Bresenham's line algorithm].
 ///
-/// Includes both the start and end point and is asymmetrical.
+/// Includes both the start and end point and is asymmetrical. See [`SymmetricBresenham`] for a
+/// version that is.
 ///
 /// Example:
 ///
@@ -23,12 +27,23 @@ use steps::Steps;
 /// (0, 0), (0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6),
 /// ```
 ///
+/// Any point along the line can be found in constant time with [`point_at`], and [`nth`] and
+/// [`skip`] jump straight to a point without walking the ones before it.
+///
+/// [`SymmetricBresenham`]: struct.SymmetricBresenham.html
+/// [`point_at`]: #method.point_at
+/// [`nth`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.nth
+/// [`skip`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.skip
 /// [Bresenham's line algorithm]: https://en.wikipedia.org/wiki/Bresenham's_line_algorithm
 pub struct Bresenham<T> {
     point: Point<T>,
+    start: Point<T>,
     end_x: T,
+    end_y: T,
+    remainder: T,
     delta_x: T,
     delta_y: T,
+    offset: T,
     error: T,
     octant: Octant,
 }
@@ -36,27 +51,221 @@ pub struct Bresenham<T> {
 impl<T: SignedNum> Bresenham<T> {
     #[inline]
     pub fn new(start: Point<T>, end: Point<T>) -> Self {
+        Self::with_rounding(start, end, |_| T::zero())
+    }
+
+    /// Create a line where the point `i` steps along is `i * delta_y + offset` divided by
+    /// `delta_x`, rounded down, with the offset calculated from `delta_x`.
+    #[inline]
+    pub(crate) fn with_rounding<F: Fn(T) -> T>(start: Point<T>, end: Point<T>, offset: F) -> Self {
         let octant = Octant::new(start, end);
         let start = octant.to(start);
         let end = octant.to(end);
 
         let delta_x = end.0 - start.0;
         let delta_y = end.1 - start.1;
+        let offset = offset(delta_x);
 
         Self {
             delta_x,
             delta_y,
+            offset,
             octant,
             point: start,
+            start,
             end_x: end.0,
-            error: delta_y - delta_x,
+            end_y: end.1,
+            remainder: offset,
+            error: delta_y - delta_x + offset,
         }
     }
 
+    /// Create a line that only contains the points that lie within `clip`.
+    ///
+    /// The points are exactly the ones that [`Bresenham::new`] would return inside of `clip`, so
+    /// a line that is split across several clip rectangles still joins up. Points outside of
+    /// `clip` are skipped over in constant time instead of being walked.
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham;
+    ///
+    /// fn main() {
+    ///     let clipped = Bresenham::clipped((-100, -50), (100, 50), ((0, 0), (4, 4)));
+    ///     assert_eq!(clipped.collect::<Vec<_>>(), [(0, 0), (1, 0), (2, 1), (3, 1), (4, 2)]);
+    /// }
+    /// ```
+    ///
+    /// [`Bresenham::new`]: struct.Bresenham.html#method.new
+    #[inline]
+    pub fn clipped(start: Point<T>, end: Point<T>, clip: Rect<T>) -> Self {
+        Self::new(start, end).clip(clip)
+    }
+
+    /// Skip the points outside of `clip`, which is given in world coordinates.
+    #[inline]
+    pub(crate) fn clip(self, clip: Rect<T>) -> Self {
+        let mut line = self;
+        let (clip_min, clip_max) = line.octant.to_rect(clip);
+        let start = line.start;
+
+        // The range of indices that lie within the clip horizontally. The endpoints can be far
+        // outside of the clip, so this is worked out in `i128` to keep the products in range.
+        let mut first = max(0, wide(clip_min.0) - wide(start.0));
+        let mut last = min(wide(line.delta_x), wide(clip_max.0) - wide(start.0));
+
+        // Narrow the range down to the indices that lie within the clip vertically.
+        // The y value at index `i` is `start.1 + (i * delta_y + offset) / delta_x`, rounded down.
+        let (delta_x, delta_y, offset) =
+            (wide(line.delta_x), wide(line.delta_y), wide(line.offset));
+        let below = wide(clip_min.1) - wide(start.1);
+        let above = wide(clip_max.1) - wide(start.1);
+
+        if below > 0 {
+            if delta_y == 0 {
+                last = -1;
+            } else {
+                first = max(first, (below * delta_x - offset + delta_y - 1) / delta_y);
+            }
+        }
+
+        if above < 0 {
+            last = -1;
+        } else if delta_y > 0 {
+            last = min(last, ((above + 1) * delta_x - offset - 1) / delta_y);
+        }
+
+        if first > last {
+            // Nothing is inside the clip, so end the line before it starts
+            line.end_x = start.0 - T::one();
+        } else {
+            line.seek(T::cast(first));
+            line.seek_back(T::cast(last));
+        }
+
+        line
+    }
+
+    /// Get the point `index` steps along from the start of the line.
+    ///
+    /// This takes the same time no matter how long the line is, and doesn't depend on how far
+    /// the line has been iterated. Returns `None` if `index` is past either end of the line.
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham;
+    ///
+    /// fn main() {
+    ///     let line = Bresenham::new((0, 0), (5, 6));
+    ///     assert_eq!(line.point_at(3), Some((2, 3)));
+    ///     assert_eq!(line.point_at(7), None);
+    /// }
+    /// ```
+    #[inline]
+    pub fn point_at(&self, index: T) -> Option<Point<T>> {
+        if index >= T::zero() && index <= self.delta_x {
+            Some(self.octant.from(self.state_at(index).0))
+        } else {
+            None
+        }
+    }
+
+    /// Get the octant-space point and error term `index` steps along from the start.
+    ///
+    /// The products are worked out in `i128`, since they can be far larger than the line.
+    #[inline]
+    fn state_at(&self, index: T) -> (Point<T>, T) {
+        let (delta_x, delta_y, offset) =
+            (wide(self.delta_x), wide(self.delta_y), wide(self.offset));
+        let rises = if index.is_zero() {
+            0
+        } else {
+            (wide(index) * delta_y + offset) / delta_x
+        };
+
+        (
+            (self.start.0 + index, self.start.1 + T::cast(rises)),
+            T::cast((wide(index) + 1) * delta_y + offset - delta_x - rises * delta_x),
+        )
+    }
+
+    /// Get the smallest and largest x values of the points on row `y`, in world coordinates.
+    ///
+    /// This covers the whole line, no matter how far it has been iterated or clipped.
+    #[inline]
+    pub(crate) fn row_at(&self, y: T) -> Option<(T, T)> {
+        let start = self.octant.from(self.start);
+
+        let (first, last, rises) = if self.octant.major_axis() == Axis::Y {
+            // Each row has a single point
+            let index = (y - start.1) * self.octant.from((T::one(), T::zero())).1;
+            let (point, _) = self.state_at(index);
+            (index, index, point.1 - self.start.1)
+        } else {
+            // Find the indices where `(index * delta_y + offset) / delta_x` is `rises`
+            let rises = (y - start.1) * self.octant.from((T::zero(), T::one())).1;
+            let (first, last) = if self.delta_y.is_zero() && rises.is_zero() {
+                (T::zero(), self.delta_x)
+            } else if self.delta_y.is_zero() {
+                (T::one(), T::zero())
+            } else {
+                let (delta_x, delta_y, offset) =
+                    (wide(self.delta_x), wide(self.delta_y), wide(self.offset));
+                let below = |rises: i128| (rises * delta_x - offset + delta_y - 1) / delta_y;
+                let (first, last) = (below(wide(rises)), below(wide(rises) + 1) - 1);
+
+                // Keep the indices in range of `T` before turning them back
+                let clamp = |index: i128| T::cast(index.max(-1).min(wide(self.delta_x) + 1));
+                (clamp(first), clamp(last))
+            };
+            (first, last, rises)
+        };
+
+        let first = max(first, T::zero());
+        let last = min(last, self.delta_x);
+
+        if first > last || rises < T::zero() || rises > self.delta_y {
+            return None;
+        }
+
+        let row = self.start.1 + rises;
+        let (a, _) = self.octant.from((self.start.0 + first, row));
+        let (b, _) = self.octant.from((self.start.0 + last, row));
+
+        Some((min(a, b), max(a, b)))
+    }
+
+    /// Move the front of the line to `index` steps along from the start.
+    #[inline]
+    fn seek(&mut self, index: T) {
+        let (point, error) = self.state_at(index);
+        self.point = point;
+        self.error = error;
+    }
+
+    /// Move the back of the line to `index` steps along from the start.
+    #[inline]
+    fn seek_back(&mut self, index: T) {
+        let (end, _) = self.state_at(index);
+        self.end_x = end.0;
+        self.end_y = end.1;
+        self.remainder = if index.is_zero() {
+            self.offset
+        } else {
+            T::cast((wide(index) * wide(self.delta_y) + wide(self.offset)) % wide(self.delta_x))
+        };
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<Point<T>, Self> {
         Steps::new(self)
     }
+
+    #[inline]
+    pub fn spans(self) -> Spans<T, Self> {
+        let axis = self.octant.major_axis();
+        Spans::new(self, axis)
+    }
 }
 
 impl<T: SignedNum> Iterator for Bresenham<T> {
@@ -80,8 +289,186 @@ impl<T: SignedNum> Iterator for Bresenham<T> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end_x - self.point.0 + T::one())
+            .max(T::zero())
+            .to_usize()
+            .unwrap();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match <T as NumCast>::from(n) {
+            Some(n) if n <= self.end_x - self.point.0 => {
+                let index = self.point.0 - self.start.0 + n;
+                self.seek(index);
+                self.next()
+            }
+            _ => {
+                self.end_x = self.point.0 - T::one();
+                None
+            }
+        }
+    }
 }
 
+impl<T: SignedNum> DoubleEndedIterator for Bresenham<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.point.0 <= self.end_x {
+            let point = self.octant.from((self.end_x, self.end_y));
+
+            // Walk the remainder of `(index * delta_y + offset) / delta_x` backwards
+            self.remainder -= self.delta_y;
+            if self.remainder < T::zero() {
+                self.remainder += self.delta_x;
+                self.end_y -= T::one();
+            }
+
+            self.end_x -= T::one();
+
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match <T as NumCast>::from(n) {
+            Some(n) if n <= self.end_x - self.point.0 => {
+                let index = self.end_x - self.start.0 - n;
+                self.seek_back(index);
+                self.next_back()
+            }
+            _ => {
+                self.end_x = self.point.0 - T::one();
+                None
+            }
+        }
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for Bresenham<T> {}
+
+/// A symmetrical version of [`Bresenham`].
+///
+/// Swapping `start` and `end` gives exactly the same points in reverse, so it's suitable for
+/// line of sight, where A seeing B should mean that B sees A. It visits the same number of points
+/// as [`Bresenham`], but rounds to the nearest point instead of always rounding down. Ties are
+/// broken as if the line was drawn from whichever endpoint is smaller.
+///
+/// Example:
+///
+/// ```rust
+/// extern crate line_drawing;
+/// use line_drawing::SymmetricBresenham;
+///
+/// fn main() {
+///     for (x, y) in SymmetricBresenham::new((0, 0), (5, 6)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 1), (2, 2), (3, 3), (3, 4), (4, 5), (5, 6),
+/// ```
+///
+/// [`Bresenham`]: struct.Bresenham.html
+pub struct SymmetricBresenham<T> {
+    line: Bresenham<T>,
+}
+
+impl<T: SignedNum> SymmetricBresenham<T> {
+    #[inline]
+    pub fn new(start: Point<T>, end: Point<T>) -> Self {
+        let forwards = start <= end;
+
+        Self {
+            line: Bresenham::with_rounding(start, end, |delta_x| {
+                // Points are found by rounding `(i * delta_y + offset) / delta_x` down, so half of
+                // `delta_x` rounds half up when drawn from the smaller endpoint, and one less than
+                // that rounds half down otherwise
+                if forwards {
+                    delta_x / T::cast(2)
+                } else {
+                    (delta_x - T::one()) / T::cast(2)
+                }
+            }),
+        }
+    }
+
+    /// Create a line that only contains the points that lie within `clip`.
+    ///
+    /// See [`Bresenham::clipped`] for details.
+    ///
+    /// [`Bresenham::clipped`]: struct.Bresenham.html#method.clipped
+    #[inline]
+    pub fn clipped(start: Point<T>, end: Point<T>, clip: Rect<T>) -> Self {
+        Self {
+            line: Self::new(start, end).line.clip(clip),
+        }
+    }
+
+    /// Get the point `index` steps along from the start of the line.
+    ///
+    /// See [`Bresenham::point_at`] for details.
+    ///
+    /// [`Bresenham::point_at`]: struct.Bresenham.html#method.point_at
+    #[inline]
+    pub fn point_at(&self, index: T) -> Option<Point<T>> {
+        self.line.point_at(index)
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Point<T>, Self> {
+        Steps::new(self)
+    }
+
+    #[inline]
+    pub fn spans(self) -> Spans<T, Self> {
+        let axis = self.line.octant.major_axis();
+        Spans::new(self, axis)
+    }
+}
+
+impl<T: SignedNum> Iterator for SymmetricBresenham<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.line.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.line.nth(n)
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for SymmetricBresenham<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.line.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.line.nth_back(n)
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for SymmetricBresenham<T> {}
+
 #[test]
 fn test() {
     assert_eq!(
@@ -89,3 +476,21 @@ fn test() {
         [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]
     )
 }
+
+#[test]
+fn clipped() {
+    let clipped = |a, b, clip| Bresenham::clipped(a, b, clip).collect::<Vec<_>>();
+
+    assert_eq!(
+        clipped((0, 0), (10, 4), ((3, 0), (6, 10))),
+        [(3, 1), (4, 1), (5, 2), (6, 2)]
+    );
+
+    assert_eq!(
+        clipped((10, 4), (0, 0), ((0, 2), (10, 2))),
+        [(5, 2), (4, 2), (3, 2)]
+    );
+
+    assert_eq!(clipped((0, 0), (5, 5), ((6, 6), (8, 8))), []);
+    assert_eq!(clipped((2, 2), (2, 2), ((0, 0), (4, 4))), [(2, 2)]);
+}
@@ -1,10 +1,12 @@
-use {SignedNum, Voxel};
+use {wide, SignedNum, Voxel};
 use steps::Steps;
 use core::cmp::max;
+use num_traits::NumCast;
 
 /// An 3-D implementation of bresenham, sourced from [this site].
 ///
-/// It includes both the start and end point and is asymmetrical.
+/// It includes both the start and end point and is asymmetrical. See [`SymmetricBresenham3d`] for
+/// a version that is.
 ///
 /// Example:
 ///
@@ -23,6 +25,14 @@ use core::cmp::max;
 /// (0, 0, 0), (1, 1, 1), (1, 2, 2), (2, 3, 3), (3, 3, 4), (4, 4, 5), (4, 5, 6), (5, 6, 7),
 /// ```
 ///
+/// Any voxel along the line can be found in constant time with [`point_at`], and [`nth`] and
+/// [`skip`] jump straight to a voxel without walking the ones before it.
+///
+/// [`SymmetricBresenham3d`]: struct.SymmetricBresenham3d.html
+/// [`point_at`]: #method.point_at
+/// [`nth`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.nth
+/// [`skip`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.skip
+///
 /// [this site]: http://members.chello.at/~easyfilter/bresenham.html
 pub struct Bresenham3d<T> {
     sign_x: T,
@@ -35,13 +45,27 @@ pub struct Bresenham3d<T> {
     len_y: T,
     len_z: T,
     longest: T,
+    offset: T,
     count: T,
+    index: T,
+    start: Voxel<T>,
     voxel: Voxel<T>,
+    back_err_x: T,
+    back_err_y: T,
+    back_err_z: T,
+    back: Voxel<T>,
 }
 
 impl<T: SignedNum> Bresenham3d<T> {
     #[inline]
     pub fn new(start: Voxel<T>, end: Voxel<T>) -> Self {
+        Self::with_rounding(start, end, |longest| longest / T::cast(2))
+    }
+
+    /// Create a line where the error terms start at an offset calculated from the length of the
+    /// longest axis, instead of at half of it.
+    #[inline]
+    pub(crate) fn with_rounding<F: Fn(T) -> T>(start: Voxel<T>, end: Voxel<T>, offset: F) -> Self {
         let delta_x = end.0 - start.0;
         let delta_y = end.1 - start.1;
         let delta_z = end.2 - start.2;
@@ -51,23 +75,93 @@ impl<T: SignedNum> Bresenham3d<T> {
         let len_z = delta_z.abs();
 
         let longest = max(len_x, max(len_y, len_z));
+        let offset = offset(longest);
 
+        // Walking back from the end, the error terms start where they did at the front
         Self {
             len_x,
             len_y,
             len_z,
             longest,
+            offset,
             count: longest,
-            err_x: longest / T::cast(2),
-            err_y: longest / T::cast(2),
-            err_z: longest / T::cast(2),
+            index: T::zero(),
+            start,
+            err_x: offset,
+            err_y: offset,
+            err_z: offset,
             sign_x: delta_x.signum(),
             sign_y: delta_y.signum(),
             sign_z: delta_z.signum(),
             voxel: start,
+            back_err_x: offset,
+            back_err_y: offset,
+            back_err_z: offset,
+            back: end,
         }
     }
 
+    /// Get the voxel `index` steps along from the start of the line.
+    ///
+    /// This takes the same time no matter how long the line is, and doesn't depend on how far
+    /// the line has been iterated. Returns `None` if `index` is past either end of the line.
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Bresenham3d;
+    ///
+    /// fn main() {
+    ///     let line = Bresenham3d::new((0, 0, 0), (5, 6, 7));
+    ///     assert_eq!(line.point_at(4), Some((3, 3, 4)));
+    ///     assert_eq!(line.point_at(8), None);
+    /// }
+    /// ```
+    #[inline]
+    pub fn point_at(&self, index: T) -> Option<Voxel<T>> {
+        if index >= T::zero() && index <= self.longest {
+            Some(self.state_at(index).0)
+        } else {
+            None
+        }
+    }
+
+    /// Get the voxel and error terms `index` steps along from the start.
+    ///
+    /// The error terms are the same whether the voxel was reached from the front or the back.
+    #[inline]
+    fn state_at(&self, index: T) -> (Voxel<T>, Voxel<T>) {
+        let (steps_x, err_x) = self.axis_at(self.len_x, index);
+        let (steps_y, err_y) = self.axis_at(self.len_y, index);
+        let (steps_z, err_z) = self.axis_at(self.len_z, index);
+
+        (
+            (
+                self.start.0 + steps_x * self.sign_x,
+                self.start.1 + steps_y * self.sign_y,
+                self.start.2 + steps_z * self.sign_z,
+            ),
+            (err_x, err_y, err_z),
+        )
+    }
+
+    /// Get the number of steps taken along an axis of length `len` after `index` steps, along
+    /// with the error term for that axis.
+    ///
+    /// The products are worked out in `i128`, since they can be far larger than the line.
+    #[inline]
+    fn axis_at(&self, len: T, index: T) -> (T, T) {
+        // Each step takes `len` off the error and each step along the axis adds `longest` back
+        let longest = wide(self.longest);
+        let excess = wide(index) * wide(len) - wide(self.offset);
+        let steps = if excess <= 0 {
+            0
+        } else {
+            (excess + longest - 1) / longest
+        };
+
+        (T::cast(steps), T::cast(steps * longest - excess))
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<Voxel<T>, Self> {
         Steps::new(self)
@@ -81,6 +175,7 @@ impl<T: SignedNum> Iterator for Bresenham3d<T> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.count >= T::zero() {
             self.count -= T::one();
+            self.index += T::one();
             self.err_x -= self.len_x;
             self.err_y -= self.len_y;
             self.err_z -= self.len_z;
@@ -107,8 +202,190 @@ impl<T: SignedNum> Iterator for Bresenham3d<T> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.count + T::one()).max(T::zero()).to_usize().unwrap();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match <T as NumCast>::from(n) {
+            Some(n) if n <= self.count => {
+                self.count -= n;
+                self.index += n;
+
+                let (voxel, (err_x, err_y, err_z)) = self.state_at(self.index);
+                self.voxel = voxel;
+                self.err_x = err_x;
+                self.err_y = err_y;
+                self.err_z = err_z;
+
+                self.next()
+            }
+            _ => {
+                self.count = -T::one();
+                None
+            }
+        }
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for Bresenham3d<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count >= T::zero() {
+            self.count -= T::one();
+            self.back_err_x += self.len_x;
+            self.back_err_y += self.len_y;
+            self.back_err_z += self.len_z;
+
+            let voxel = self.back;
+
+            // Undo the steps that brought the error terms back into range
+            if self.back_err_x >= self.longest {
+                self.back_err_x -= self.longest;
+                self.back.0 -= self.sign_x;
+            }
+
+            if self.back_err_y >= self.longest {
+                self.back_err_y -= self.longest;
+                self.back.1 -= self.sign_y;
+            }
+
+            if self.back_err_z >= self.longest {
+                self.back_err_z -= self.longest;
+                self.back.2 -= self.sign_z;
+            }
+
+            Some(voxel)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match <T as NumCast>::from(n) {
+            Some(n) if n <= self.count => {
+                self.count -= n;
+
+                let (voxel, (err_x, err_y, err_z)) = self.state_at(self.index + self.count);
+                self.back = voxel;
+                self.back_err_x = err_x;
+                self.back_err_y = err_y;
+                self.back_err_z = err_z;
+
+                self.next_back()
+            }
+            _ => {
+                self.count = -T::one();
+                None
+            }
+        }
+    }
 }
 
+impl<T: SignedNum> ExactSizeIterator for Bresenham3d<T> {}
+
+/// A symmetrical version of [`Bresenham3d`].
+///
+/// Swapping `start` and `end` gives exactly the same voxels in reverse, so it's suitable for
+/// line of sight, where A seeing B should mean that B sees A. It visits the same number of voxels
+/// as [`Bresenham3d`], and ties are broken as if the line was drawn from whichever endpoint is
+/// smaller.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::SymmetricBresenham3d;
+///
+/// fn main() {
+///     for (x, y, z) in SymmetricBresenham3d::new((0, 0, 0), (5, 6, 7)) {
+///         print!("({}, {}, {}), ", x, y, z);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0, 0), (1, 1, 1), (1, 2, 2), (2, 3, 3), (3, 3, 4), (4, 4, 5), (4, 5, 6), (5, 6, 7),
+/// ```
+///
+/// [`Bresenham3d`]: struct.Bresenham3d.html
+pub struct SymmetricBresenham3d<T> {
+    line: Bresenham3d<T>,
+}
+
+impl<T: SignedNum> SymmetricBresenham3d<T> {
+    #[inline]
+    pub fn new(start: Voxel<T>, end: Voxel<T>) -> Self {
+        let forwards = start <= end;
+
+        Self {
+            line: Bresenham3d::with_rounding(start, end, |longest| {
+                // An axis only steps once its error term drops below zero, so starting it at half
+                // of `longest` rounds half down when drawn from the smaller endpoint, and one less
+                // than that rounds half up otherwise
+                if forwards {
+                    longest / T::cast(2)
+                } else {
+                    (longest - T::one()) / T::cast(2)
+                }
+            }),
+        }
+    }
+
+    /// Get the voxel `index` steps along from the start of the line.
+    ///
+    /// See [`Bresenham3d::point_at`] for details.
+    ///
+    /// [`Bresenham3d::point_at`]: struct.Bresenham3d.html#method.point_at
+    #[inline]
+    pub fn point_at(&self, index: T) -> Option<Voxel<T>> {
+        self.line.point_at(index)
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<Voxel<T>, Self> {
+        Steps::new(self)
+    }
+}
+
+impl<T: SignedNum> Iterator for SymmetricBresenham3d<T> {
+    type Item = Voxel<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.line.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.line.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.line.nth(n)
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for SymmetricBresenham3d<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.line.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.line.nth_back(n)
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for SymmetricBresenham3d<T> {}
+
 #[test]
 fn tests() {
     assert_eq!(
@@ -3,7 +3,9 @@ use {Point, SignedNum};
 /// An implementation of [Bresenham's circle algorithm].
 ///
 /// This uses four quadrants, so calling `next()` will return a point for the first quadrant,
-/// then the second, third, fourth and then back to first.
+/// then the second, third, fourth and then back to first. Each point is only returned once. To get
+/// the points in order around the circle instead, as one 8-connected loop, use
+/// [`Arc::whole_circle`].
 ///
 /// Example:
 ///
@@ -23,6 +25,7 @@ use {Point, SignedNum};
 /// ```
 ///
 /// [Bresenham's circle algorithm]: http://members.chello.at/~easyfilter/bresenham.html
+/// [`Arc::whole_circle`]: struct.Arc.html#method.whole_circle
 pub struct BresenhamCircle<T> {
     x: T,
     y: T,
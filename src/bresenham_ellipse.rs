@@ -0,0 +1,200 @@
+use {Point, Rect, SignedNum};
+use core::cmp::{max, min};
+
+/// An implementation of Bresenham's algorithm for axis-aligned ellipses, in the form given by
+/// [Alois Zingl].
+///
+/// Like [`BresenhamCircle`], this uses four quadrants, so calling `next()` will return a point
+/// for the first quadrant, then the second, third, fourth and then back to first. Points that
+/// would be the same in more than one quadrant, at the ends of the axes, are only returned once.
+///
+/// The ellipse can be given by its centre and radii with [`new`], or by the rectangle it fits in
+/// with [`from_rect`], which can also be an even number of pixels wide or tall. Very flat
+/// ellipses, where the usual algorithm stops before reaching the tips, are finished off with a
+/// straight run of pixels so that there are no gaps.
+///
+/// The error terms are worked out with `i64`, so the ellipse can be up to about 40,000 pixels
+/// across whatever type the points are.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::BresenhamEllipse;
+///
+/// fn main() {
+///     for (x, y) in BresenhamEllipse::new(0, 0, 3, 1) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (3, 0), (-3, 0), (2, 1), (-2, 1), (-2, -1), (2, -1), (1, 1), (-1, 1), (-1, -1), (1, -1), (0, 1), (0, -1),
+/// ```
+///
+/// [Alois Zingl]: http://members.chello.at/~easyfilter/bresenham.html
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+/// [`new`]: #method.new
+/// [`from_rect`]: #method.from_rect
+pub struct BresenhamEllipse<T> {
+    left: T,
+    right: T,
+    top: T,
+    bottom: T,
+    height: T,
+    step_x: i64,
+    step_y: i64,
+    increase_x: i64,
+    increase_y: i64,
+    error: i64,
+    stepped_y: bool,
+    tip: bool,
+    quadrant: u8,
+}
+
+impl<T: SignedNum> BresenhamEllipse<T> {
+    #[inline]
+    pub fn new(center_x: T, center_y: T, radius_x: T, radius_y: T) -> Self {
+        Self::from_rect((
+            (center_x - radius_x, center_y - radius_y),
+            (center_x + radius_x, center_y + radius_y),
+        ))
+    }
+
+    /// Make the largest ellipse that fits inside of `rect`, touching each of its sides.
+    #[inline]
+    pub fn from_rect(rect: Rect<T>) -> Self {
+        let (a, b) = rect;
+        let (left, right) = (min(a.0, b.0), max(a.0, b.0));
+        let (bottom, top) = (min(a.1, b.1), max(a.1, b.1));
+
+        let width = (right - left).to_i64().unwrap();
+        let height = (top - bottom).to_i64().unwrap();
+        let odd = height & 1;
+
+        // Start in the middle row, or the middle two rows if there's an even number of them
+        let top = bottom + T::cast((height + 1) / 2);
+        let bottom = top - T::cast(odd);
+
+        let step_x = 4 * (1 - width) * height * height;
+        let step_y = 4 * (odd + 1) * width * width;
+
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+            height: T::cast(height),
+            step_x,
+            step_y,
+            increase_x: 8 * height * height,
+            increase_y: 8 * width * width,
+            error: step_x + step_y + odd * width * width,
+            stepped_y: true,
+            tip: false,
+            quadrant: 1,
+        }
+    }
+
+    /// Move on to the next set of quadrants.
+    #[inline]
+    fn step(&mut self) {
+        if self.tip {
+            self.top += T::one();
+            self.bottom -= T::one();
+            return;
+        }
+
+        let error = self.error * 2;
+        self.stepped_y = error <= self.step_y;
+
+        if self.stepped_y {
+            self.top += T::one();
+            self.bottom -= T::one();
+            self.step_y += self.increase_y;
+            self.error += self.step_y;
+        }
+
+        if error >= self.step_x || self.error * 2 > self.step_y {
+            self.left += T::one();
+            self.right -= T::one();
+            self.step_x += self.increase_x;
+            self.error += self.step_x;
+        }
+
+        // Finish off the tips of flat ellipses, skipping the rows that have already been drawn
+        if self.left > self.right {
+            self.tip = true;
+
+            if !self.stepped_y {
+                self.top += T::one();
+                self.bottom -= T::one();
+            }
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for BresenhamEllipse<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (left, right) = if self.tip {
+                (self.left - T::one(), self.right + T::one())
+            } else {
+                (self.left, self.right)
+            };
+
+            if self.tip && self.top - self.bottom > self.height {
+                return None;
+            }
+
+            // Skip the points that are the same as one in an earlier quadrant
+            let point = match self.quadrant {
+                1 => Some((right, self.top)),
+                2 if left != right => Some((left, self.top)),
+                3 if self.top != self.bottom => Some((left, self.bottom)),
+                4 if self.top != self.bottom && left != right => Some((right, self.bottom)),
+                _ => None,
+            };
+
+            if self.quadrant == 4 {
+                self.step();
+            }
+
+            self.quadrant = self.quadrant % 4 + 1;
+
+            if point.is_some() {
+                return point;
+            }
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    let ellipse = |rect| BresenhamEllipse::from_rect(rect).collect::<Vec<_>>();
+
+    // An even-sized rectangle
+    assert_eq!(
+        ellipse(((0, 0), (3, 3))),
+        [
+            (3, 2),
+            (0, 2),
+            (0, 1),
+            (3, 1),
+            (2, 3),
+            (1, 3),
+            (1, 0),
+            (2, 0)
+        ]
+    );
+
+    // A flat ellipse fills in its whole outline
+    assert_eq!(ellipse(((0, 0), (5, 1))).len(), 12);
+    assert_eq!(ellipse(((0, 0), (1, 5))).len(), 12);
+
+    assert_eq!(ellipse(((2, 3), (2, 3))), [(2, 3)]);
+}
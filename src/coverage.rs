@@ -0,0 +1,218 @@
+use {FloatNum, Point};
+
+/// How to turn the coverage from an anti-aliased iterator into a value to draw with.
+///
+/// Coverage is linear, so a pixel that's half covered has a value of `0.5`, but most images and
+/// displays expect values that have been encoded with a gamma curve. Drawing coverage straight
+/// into an sRGB image makes anti-aliased edges look too thin and dark, and makes lines look like
+/// they're made of beads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transfer<I> {
+    /// Keep the coverage as it is, for drawing into a linear image.
+    Linear,
+    /// Encode the coverage with a simple power curve for a display with this gamma, such as `2.2`.
+    Gamma(I),
+    /// Encode the coverage with the [sRGB transfer function].
+    ///
+    /// [sRGB transfer function]: https://en.wikipedia.org/wiki/SRGB#Transformation
+    Srgb,
+}
+
+impl<I: FloatNum> Transfer<I> {
+    /// Turn `coverage` into a value to draw with, clamping it between zero and one first.
+    #[inline]
+    pub fn apply(&self, coverage: I) -> I {
+        let coverage = coverage.max(I::zero()).min(I::one());
+
+        match *self {
+            Transfer::Linear => coverage,
+            Transfer::Gamma(gamma) => coverage.powf(gamma.recip()),
+            Transfer::Srgb => {
+                if coverage <= I::cast(0.003_130_8) {
+                    coverage * I::cast(12.92)
+                } else {
+                    I::cast(1.055) * coverage.powf(I::cast(1.0 / 2.4)) - I::cast(0.055)
+                }
+            }
+        }
+    }
+}
+
+/// The integer types that a value between zero and one can be quantized to.
+pub trait Quantize: Copy + Default {
+    /// Scale `value` from `0.0..=1.0` to the full range of the type, rounding to the nearest
+    /// step. Values outside of that range are clamped.
+    fn quantize<I: FloatNum>(value: I) -> Self;
+}
+
+impl Quantize for u8 {
+    #[inline]
+    fn quantize<I: FloatNum>(value: I) -> Self {
+        let value = value.max(I::zero()).min(I::one());
+        (value * I::cast(Self::MAX)).round().to_u8().unwrap()
+    }
+}
+
+impl Quantize for u16 {
+    #[inline]
+    fn quantize<I: FloatNum>(value: I) -> Self {
+        let value = value.max(I::zero()).min(I::one());
+        (value * I::cast(Self::MAX)).round().to_u16().unwrap()
+    }
+}
+
+/// A lookup table that quantizes coverage and applies a [`Transfer`] in one step.
+///
+/// Raising numbers to a power for every pixel can be slow, so this works the [`Transfer`] out
+/// ahead of time for 256 evenly spaced coverage values. Looking up a value rounds the coverage to
+/// the nearest of those, which loses a little precision when building a `u16` table.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{AlphaTable, Transfer};
+///
+/// fn main() {
+///     let table = AlphaTable::<u8>::new(Transfer::Srgb);
+///
+///     for &coverage in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+///         print!("{}, ", table.lookup(coverage));
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// 0, 137, 188, 224, 255,
+/// ```
+///
+/// [`Transfer`]: enum.Transfer.html
+pub struct AlphaTable<T> {
+    values: [T; 256],
+}
+
+impl<T: Quantize> AlphaTable<T> {
+    #[inline]
+    pub fn new(transfer: Transfer<f64>) -> Self {
+        let mut values = [T::default(); 256];
+
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = T::quantize(transfer.apply(i as f64 / 255.0));
+        }
+
+        Self { values }
+    }
+
+    /// Get the quantized value for `coverage`.
+    #[inline]
+    pub fn lookup<I: FloatNum>(&self, coverage: I) -> T {
+        self.values[usize::from(u8::quantize(coverage))]
+    }
+}
+
+/// Functions for changing the coverage of any iterator that returns `(point, coverage)` pairs,
+/// like [`XiaolinWu`] or [`GuptaSproull`].
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{AntiAliased, Quantize, XiaolinWu};
+///
+/// fn main() {
+///     for ((x, y), value) in XiaolinWu::<f32, i8>::new((0.0, 0.0), (4.0, 1.0)).with_gamma(2.2) {
+///         print!("(({}, {}), {}), ", x, y, u8::quantize(value));
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, 0), 186), ((1, 0), 224), ((1, 1), 136), ((2, 0), 186), ((2, 1), 186), ((3, 0), 136), ((3, 1), 224), ((4, 1), 186),
+/// ```
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`GuptaSproull`]: struct.GuptaSproull.html
+pub trait AntiAliased<O, I: FloatNum>: Iterator<Item = (Point<O>, I)> + Sized {
+    /// Encode the coverage with a [`Transfer`].
+    ///
+    /// [`Transfer`]: enum.Transfer.html
+    #[inline]
+    fn with_transfer(self, transfer: Transfer<I>) -> Transferred<Self, I> {
+        Transferred {
+            iterator: self,
+            transfer,
+        }
+    }
+
+    /// Encode the coverage for a display with a gamma of `gamma`.
+    #[inline]
+    fn with_gamma(self, gamma: I) -> Transferred<Self, I> {
+        self.with_transfer(Transfer::Gamma(gamma))
+    }
+
+    /// Encode the coverage with the sRGB transfer function.
+    #[inline]
+    fn with_srgb(self) -> Transferred<Self, I> {
+        self.with_transfer(Transfer::Srgb)
+    }
+}
+
+impl<O, I: FloatNum, T: Iterator<Item = (Point<O>, I)>> AntiAliased<O, I> for T {}
+
+/// An iterator with its coverage encoded by a [`Transfer`].
+///
+/// [`Transfer`]: enum.Transfer.html
+pub struct Transferred<T, I> {
+    iterator: T,
+    transfer: Transfer<I>,
+}
+
+impl<O, I: FloatNum, T: Iterator<Item = (Point<O>, I)>> Iterator for Transferred<T, I> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (point, coverage) = self.iterator.next()?;
+        Some((point, self.transfer.apply(coverage)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iterator.size_hint()
+    }
+}
+
+#[test]
+fn tests() {
+    use XiaolinWu;
+
+    assert_eq!(Transfer::Linear.apply(0.25), 0.25);
+    assert_eq!(Transfer::Gamma(2.0).apply(0.25), 0.5);
+    assert!((Transfer::Srgb.apply(1.5f64) - 1.0).abs() < 1e-9);
+    assert!((Transfer::Srgb.apply(0.5f64) - 0.735_357).abs() < 1e-6);
+
+    assert_eq!(u8::quantize(0.5), 128);
+    assert_eq!(u8::quantize(-1.0), 0);
+    assert_eq!(u16::quantize(1.0), 65535);
+
+    // The table gives the same values as working them out directly
+    let table = AlphaTable::<u16>::new(Transfer::Gamma(2.2));
+    for i in 0..256 {
+        let coverage = i as f64 / 255.0;
+        assert_eq!(
+            table.lookup(coverage),
+            u16::quantize(Transfer::Gamma(2.2).apply(coverage))
+        );
+    }
+
+    assert_eq!(
+        XiaolinWu::<f64, i32>::new((0.0, 0.0), (2.0, 0.0))
+            .with_gamma(2.0)
+            .collect::<Vec<_>>(),
+        [
+            ((0, 0), 0.5f64.sqrt()),
+            ((1, 0), 1.0),
+            ((2, 0), 0.5f64.sqrt())
+        ]
+    );
+}
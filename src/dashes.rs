@@ -0,0 +1,283 @@
+use {FloatNum, Point, SignedNum, XiaolinWu};
+use num_traits::NumCast;
+
+/// How the lengths in a [`DashPattern`] are measured.
+///
+/// [`DashPattern`]: struct.DashPattern.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashMeasure {
+    /// Each point along the line counts as a length of one.
+    Steps,
+    /// The straight-line distance between each point and the one before it.
+    Length,
+}
+
+/// An on/off dash pattern that keeps its place between lines.
+///
+/// The lengths alternate between dashes that are drawn and gaps that are skipped, starting with a
+/// dash. An odd number of lengths is repeated to make it even, so `[3.0]` means three on and
+/// three off. Lengths should not be negative, and a pattern whose lengths add up to zero is
+/// treated as solid.
+///
+/// Applying the pattern to a line with [`dash`] (or [`dash_xiaolin_wu`]) borrows it for as long as
+/// the line is being iterated, and the next line picks the pattern up where that one finished, so
+/// a dashed polyline doesn't restart at every corner. When measuring in steps, skip the first
+/// point of each line after the first so the shared corners aren't counted twice.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{Bresenham, DashMeasure, DashPattern};
+///
+/// fn main() {
+///     let mut pattern = DashPattern::new(&[2.0, 1.0], DashMeasure::Steps);
+///
+///     for (x, y) in pattern.dash(Bresenham::new((0, 0), (4, 0))) {
+///         print!("({}, {}), ", x, y);
+///     }
+///
+///     for (x, y) in pattern.dash(Bresenham::new((4, 0), (4, 4)).skip(1)) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (3, 0), (4, 0), (4, 2), (4, 3),
+/// ```
+///
+/// [`dash`]: #method.dash
+/// [`dash_xiaolin_wu`]: #method.dash_xiaolin_wu
+pub struct DashPattern<'a, F> {
+    lengths: &'a [F],
+    measure: DashMeasure,
+    period: F,
+    phase: F,
+    dash: usize,
+    dash_end: F,
+    last: Option<Point<F>>,
+}
+
+impl<'a, F: FloatNum> DashPattern<'a, F> {
+    #[inline]
+    pub fn new(lengths: &'a [F], measure: DashMeasure) -> Self {
+        let count = Self::count(lengths);
+        let period = (0..count).fold(F::zero(), |total, dash| {
+            total + lengths[dash % lengths.len()]
+        });
+
+        Self {
+            lengths,
+            measure,
+            period,
+            phase: F::zero(),
+            dash: 0,
+            dash_end: lengths.first().cloned().unwrap_or_else(F::zero),
+            last: None,
+        }
+    }
+
+    /// Start the pattern `offset` along, instead of at the start of the first dash.
+    #[inline]
+    pub fn with_offset(mut self, offset: F) -> Self {
+        self.advance(offset);
+        self
+    }
+
+    /// Only return the points of `points` that land on a dash.
+    #[inline]
+    pub fn dash<T, I>(&mut self, points: I) -> Dashed<'_, 'a, F, I::IntoIter>
+    where
+        T: NumCast + Copy,
+        I: IntoIterator<Item = Point<T>>,
+    {
+        Dashed {
+            pattern: self,
+            iterator: points.into_iter(),
+        }
+    }
+
+    /// Only return the points of a [`XiaolinWu`] line that land on a dash.
+    ///
+    /// The pattern moves along the centre of the line, once for each step along its major axis,
+    /// so both points of an anti-aliased pair are either drawn or skipped together.
+    ///
+    /// [`XiaolinWu`]: struct.XiaolinWu.html
+    #[inline]
+    pub fn dash_xiaolin_wu<O: SignedNum>(
+        &mut self,
+        line: XiaolinWu<F, O>,
+    ) -> DashedXiaolinWu<'_, 'a, F, O> {
+        DashedXiaolinWu {
+            pattern: self,
+            line,
+            column: None,
+            on: false,
+        }
+    }
+
+    /// Check if the current position is on a dash.
+    #[inline]
+    fn is_on(&self) -> bool {
+        self.period <= F::zero() || self.dash & 1 == 0
+    }
+
+    /// Move to `point`, and check if it lands on a dash.
+    #[inline]
+    fn move_to(&mut self, point: Point<F>) -> bool {
+        if let Some(last) = self.last {
+            let distance = match self.measure {
+                DashMeasure::Steps => F::one(),
+                DashMeasure::Length => (point.0 - last.0).hypot(point.1 - last.1),
+            };
+
+            self.advance(distance);
+        }
+
+        self.last = Some(point);
+        self.is_on()
+    }
+
+    /// Move `distance` along the pattern.
+    #[inline]
+    fn advance(&mut self, distance: F) {
+        if self.period <= F::zero() {
+            return;
+        }
+
+        self.phase += distance;
+
+        if self.phase >= self.period {
+            self.phase %= self.period;
+            self.dash = 0;
+            self.dash_end = self.lengths[0];
+        }
+
+        let count = Self::count(self.lengths);
+
+        while self.phase >= self.dash_end && self.dash + 1 < count {
+            self.dash += 1;
+            self.dash_end += self.lengths[self.dash % self.lengths.len()];
+        }
+    }
+
+    /// Get the number of lengths in one repeat of the pattern.
+    #[inline]
+    fn count(lengths: &[F]) -> usize {
+        lengths.len() * (1 + lengths.len() % 2)
+    }
+}
+
+/// The points of a line that land on a [`DashPattern`].
+///
+/// [`DashPattern`]: struct.DashPattern.html
+pub struct Dashed<'p, 'a: 'p, F: 'p + 'a, I> {
+    pattern: &'p mut DashPattern<'a, F>,
+    iterator: I,
+}
+
+impl<'p, 'a, F, T, I> Iterator for Dashed<'p, 'a, F, I>
+where
+    F: FloatNum,
+    T: NumCast + Copy,
+    I: Iterator<Item = Point<T>>,
+{
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pattern = &mut self.pattern;
+
+        self.iterator
+            .find(|point| pattern.move_to((F::cast(point.0), F::cast(point.1))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iterator.size_hint().1)
+    }
+}
+
+/// The points of a [`XiaolinWu`] line that land on a [`DashPattern`].
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+/// [`DashPattern`]: struct.DashPattern.html
+pub struct DashedXiaolinWu<'p, 'a: 'p, F: 'p + 'a, O> {
+    pattern: &'p mut DashPattern<'a, F>,
+    line: XiaolinWu<F, O>,
+    column: Option<O>,
+    on: bool,
+}
+
+impl<'p, 'a, F: FloatNum, O: SignedNum> Iterator for DashedXiaolinWu<'p, 'a, F, O> {
+    type Item = (Point<O>, F);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (column, centre) = self.line.position();
+            let item = self.line.next()?;
+
+            // Only move the pattern along when the line moves onto a new column
+            if self.column != Some(column) {
+                self.column = Some(column);
+                self.on = self.pattern.move_to(centre);
+            }
+
+            if self.on {
+                return Some(item);
+            }
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use {Bresenham, WalkGrid};
+
+    let mut pattern = DashPattern::new(&[1.0, 2.0], DashMeasure::Steps);
+    assert_eq!(
+        pattern
+            .dash(Bresenham::new((0, 0), (6, 0)))
+            .collect::<Vec<_>>(),
+        [(0, 0), (3, 0), (6, 0)]
+    );
+
+    // Carry on from where the last line stopped, without counting the corner twice
+    assert_eq!(
+        pattern
+            .dash(WalkGrid::new((6, 0), (6, 6)).skip(1))
+            .collect::<Vec<_>>(),
+        [(6, 3), (6, 6)]
+    );
+
+    // Odd patterns repeat to become even
+    let mut pattern = DashPattern::new(&[2.0], DashMeasure::Steps).with_offset(1.0);
+    assert_eq!(
+        pattern
+            .dash(Bresenham::new((0, 0), (6, 0)))
+            .collect::<Vec<_>>(),
+        [(0, 0), (3, 0), (4, 0)]
+    );
+
+    // Diagonal steps are longer
+    let mut pattern = DashPattern::new(&[2.0, 1.0], DashMeasure::Length);
+    assert_eq!(
+        pattern
+            .dash(Bresenham::new((0, 0), (4, 4)))
+            .collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (3, 3)]
+    );
+
+    let mut pattern = DashPattern::<f32>::new(&[], DashMeasure::Length);
+    assert_eq!(pattern.dash(Bresenham::new((0, 0), (4, 0))).count(), 5);
+
+    let mut pattern = DashPattern::new(&[1.0, 1.0], DashMeasure::Steps);
+    assert_eq!(
+        pattern
+            .dash_xiaolin_wu(XiaolinWu::<f32, i8>::new((0.0, 0.0), (4.0, 2.0)))
+            .collect::<Vec<_>>(),
+        [((0, 0), 0.5), ((2, 1), 1.0), ((4, 2), 0.5)]
+    );
+}
@@ -0,0 +1,302 @@
+use {FloatNum, Point, Rect, SignedNum};
+use core::cmp::{max, min};
+
+/// A shape that a [`DistanceField`] measures the distance to.
+///
+/// [`DistanceField`]: struct.DistanceField.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceShape<I> {
+    /// A line segment between two points.
+    Segment(Point<I>, Point<I>),
+    /// A circle with a centre and a radius, like the ones drawn by [`BresenhamCircle`].
+    ///
+    /// [`BresenhamCircle`]: struct.BresenhamCircle.html
+    Circle(Point<I>, I),
+}
+
+impl<I: FloatNum> DistanceShape<I> {
+    /// Get the distance from `point` to the nearest point on the shape.
+    #[inline]
+    fn distance(&self, point: Point<I>) -> I {
+        match *self {
+            DistanceShape::Segment(start, end) => {
+                let delta = (end.0 - start.0, end.1 - start.1);
+                let offset = (point.0 - start.0, point.1 - start.1);
+                let length = delta.0 * delta.0 + delta.1 * delta.1;
+
+                // Find the nearest point along the segment, clamped to its ends
+                let t = if length.is_zero() {
+                    I::zero()
+                } else {
+                    ((offset.0 * delta.0 + offset.1 * delta.1) / length)
+                        .max(I::zero())
+                        .min(I::one())
+                };
+
+                (offset.0 - delta.0 * t).hypot(offset.1 - delta.1 * t)
+            }
+            DistanceShape::Circle(center, radius) => {
+                ((point.0 - center.0).hypot(point.1 - center.1) - radius.abs()).abs()
+            }
+        }
+    }
+
+    /// Check if a ray from `point` towards positive x crosses the shape an odd number of times.
+    #[inline]
+    fn crosses(&self, point: Point<I>) -> bool {
+        match *self {
+            DistanceShape::Segment(start, end) => {
+                (start.1 <= point.1) != (end.1 <= point.1)
+                    && point.0
+                        < start.0 + (point.1 - start.1) / (end.1 - start.1) * (end.0 - start.0)
+            }
+            DistanceShape::Circle(center, radius) => {
+                (point.0 - center.0).hypot(point.1 - center.1) < radius.abs()
+            }
+        }
+    }
+
+    /// Get the lowest and highest corners of the shape.
+    #[inline]
+    fn bounds(&self) -> (Point<I>, Point<I>) {
+        match *self {
+            DistanceShape::Segment(start, end) => (
+                (start.0.min(end.0), start.1.min(end.1)),
+                (start.0.max(end.0), start.1.max(end.1)),
+            ),
+            DistanceShape::Circle(center, radius) => {
+                let radius = radius.abs();
+                (
+                    (center.0 - radius, center.1 - radius),
+                    (center.0 + radius, center.1 + radius),
+                )
+            }
+        }
+    }
+}
+
+/// The exact Euclidean distance from every cell in a rectangle to the nearest of a set of shapes.
+///
+/// Cells are the integer points of the grid, so the distance is measured from the centre of each
+/// pixel. Only the cells within `padding` of the bounding box of the shapes are returned, so the
+/// time taken depends on the size of that box and the number of shapes, and not on the size of the
+/// image being drawn to. This can be cut down further to the image with [`clip`].
+///
+/// A distance field made with [`signed`] treats the shapes as the outlines of closed shapes, and
+/// returns negative distances for the cells inside them. Like the even-odd fill rule, a cell is
+/// inside if a ray from it crosses the segments an odd number of times, with the inside of each
+/// circle counting as one more crossing. The segments should join up into closed loops.
+///
+/// The cells are returned a row at a time in order of increasing y, and from left to right within
+/// each row.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{DistanceField, DistanceShape};
+///
+/// fn main() {
+///     let shapes = [DistanceShape::Circle((0.0, 0.0), 1.0)];
+///
+///     for ((x, y), distance) in DistanceField::<f32, i8>::signed(&shapes, 0.5) {
+///         print!("(({}, {}), {:.2}), ", x, y, distance);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((-1, -1), 0.41), ((0, -1), 0.00), ((1, -1), 0.41), ((-1, 0), 0.00), ((0, 0), -1.00), ((1, 0), 0.00), ((-1, 1), 0.41), ((0, 1), 0.00), ((1, 1), 0.41),
+/// ```
+///
+/// [`clip`]: #method.clip
+/// [`signed`]: #method.signed
+pub struct DistanceField<'a, I: 'a, O> {
+    shapes: &'a [DistanceShape<I>],
+    signed: bool,
+    start_x: O,
+    x: O,
+    end_x: O,
+    y: O,
+    end_y: O,
+}
+
+impl<'a, I: FloatNum, O: SignedNum> DistanceField<'a, I, O> {
+    #[inline]
+    pub fn new(shapes: &'a [DistanceShape<I>], padding: I) -> Self {
+        let (low, high) = shapes.iter().fold(
+            (
+                (I::infinity(), I::infinity()),
+                (I::neg_infinity(), I::neg_infinity()),
+            ),
+            |(low, high), shape| {
+                let (shape_low, shape_high) = shape.bounds();
+                (
+                    (low.0.min(shape_low.0), low.1.min(shape_low.1)),
+                    (high.0.max(shape_high.0), high.1.max(shape_high.1)),
+                )
+            },
+        );
+
+        let field = Self {
+            shapes,
+            signed: false,
+            start_x: O::one(),
+            x: O::one(),
+            end_x: O::zero(),
+            y: O::one(),
+            end_y: O::zero(),
+        };
+
+        if shapes.is_empty() {
+            return field;
+        }
+
+        let padding = padding.abs();
+        let start_x = O::cast((low.0 - padding).ceil());
+
+        Self {
+            start_x,
+            x: start_x,
+            end_x: O::cast((high.0 + padding).floor()),
+            y: O::cast((low.1 - padding).ceil()),
+            end_y: O::cast((high.1 + padding).floor()),
+            ..field
+        }
+    }
+
+    /// Make a distance field that is negative inside of the shapes.
+    #[inline]
+    pub fn signed(shapes: &'a [DistanceShape<I>], padding: I) -> Self {
+        Self {
+            signed: true,
+            ..Self::new(shapes, padding)
+        }
+    }
+
+    /// Only return the cells inside of `clip`, as well as within the padding around the shapes.
+    #[inline]
+    pub fn clip(self, clip: Rect<O>) -> Self {
+        let (clip_min, clip_max) = clip;
+        let start_x = max(self.start_x, clip_min.0);
+
+        Self {
+            start_x,
+            x: start_x,
+            end_x: min(self.end_x, clip_max.0),
+            y: max(self.y, clip_min.1),
+            end_y: min(self.end_y, clip_max.1),
+            ..self
+        }
+    }
+
+    /// Get the rectangle of cells that are returned, or `None` if there aren't any.
+    #[inline]
+    pub fn rect(&self) -> Option<Rect<O>> {
+        if self.start_x <= self.end_x && self.y <= self.end_y {
+            Some(((self.start_x, self.y), (self.end_x, self.end_y)))
+        } else {
+            None
+        }
+    }
+
+    /// Get the distance from `point` to the nearest shape.
+    #[inline]
+    fn distance(&self, point: Point<I>) -> I {
+        let distance = self.shapes.iter().fold(I::infinity(), |nearest, shape| {
+            nearest.min(shape.distance(point))
+        });
+
+        let crossings = || {
+            self.shapes
+                .iter()
+                .filter(|shape| shape.crosses(point))
+                .count()
+        };
+
+        // Cells right on an outline are neither inside nor outside
+        if self.signed && !distance.is_zero() && crossings() & 1 == 1 {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum> Iterator for DistanceField<'a, I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x > self.end_x {
+            self.x = self.start_x;
+            self.y += O::one();
+        }
+
+        if self.y > self.end_y || self.start_x > self.end_x {
+            return None;
+        }
+
+        let point = (self.x, self.y);
+        self.x += O::one();
+
+        Some((point, self.distance((I::cast(point.0), I::cast(point.1)))))
+    }
+}
+
+#[test]
+fn tests() {
+    let field =
+        |shapes, padding| DistanceField::<f64, i32>::new(shapes, padding).collect::<Vec<_>>();
+
+    // The distance to a segment is measured to its nearest end past either side
+    assert_eq!(
+        field(&[DistanceShape::Segment((0.0, 0.0), (2.0, 0.0))], 1.0),
+        [
+            ((-1, -1), 2.0f64.sqrt()),
+            ((0, -1), 1.0),
+            ((1, -1), 1.0),
+            ((2, -1), 1.0),
+            ((3, -1), 2.0f64.sqrt()),
+            ((-1, 0), 1.0),
+            ((0, 0), 0.0),
+            ((1, 0), 0.0),
+            ((2, 0), 0.0),
+            ((3, 0), 1.0),
+            ((-1, 1), 2.0f64.sqrt()),
+            ((0, 1), 1.0),
+            ((1, 1), 1.0),
+            ((2, 1), 1.0),
+            ((3, 1), 2.0f64.sqrt())
+        ]
+    );
+
+    // The inside of a closed shape is negative, and a circle inside of it is outside again
+    let square = [(-3.0, -3.0), (3.0, -3.0), (3.0, 3.0), (-3.0, 3.0)];
+    let mut shapes = [DistanceShape::Circle((0.0, 0.0), 1.0); 5];
+    for i in 0..4 {
+        shapes[i] = DistanceShape::Segment(square[i], square[(i + 1) % 4]);
+    }
+
+    let signed = |point: Point<i32>| {
+        DistanceField::<f64, i32>::signed(&shapes, 0.0)
+            .clip((point, point))
+            .next()
+            .unwrap()
+            .1
+    };
+
+    assert_eq!(signed((0, 0)), 1.0);
+    assert_eq!(signed((2, 0)), -1.0);
+    assert_eq!(signed((0, -3)), 0.0);
+    assert_eq!(signed((-2, 2)), -1.0);
+
+    let bounded = DistanceField::<f64, i32>::signed(&shapes, 2.0);
+    assert_eq!(bounded.rect(), Some(((-5, -5), (5, 5))));
+    assert_eq!(
+        bounded.clip(((0, 4), (10, 10))).rect(),
+        Some(((0, 4), (5, 5)))
+    );
+
+    assert_eq!(field(&[], 1.0), []);
+}
@@ -0,0 +1,334 @@
+use {Point, SignedNum};
+use core::cmp::{max, min};
+
+/// The rows of the quarter of a [`BresenhamCircle`] with positive x and y, centred on zero.
+///
+/// Each row is returned as `(row, low, high)`, where `low` and `high` are the smallest and largest
+/// x values of the circle's points in that row. The first quadrant of the circle is walked in the
+/// same way as [`BresenhamCircle`], and neighbouring points are grouped into rows. Going up, the
+/// rows run from zero to the radius. Going down, the rows run from the radius down to one, using
+/// the points with x and y swapped, which gives the same circle since it's symmetrical.
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+struct Rows<T> {
+    radius: T,
+    x: T,
+    y: T,
+    error: T,
+    next: Option<Point<T>>,
+    up: bool,
+    top: bool,
+}
+
+impl<T: SignedNum> Rows<T> {
+    #[inline]
+    fn new(radius: T, up: bool) -> Self {
+        let mut rows = Self {
+            radius,
+            x: -radius,
+            y: T::zero(),
+            error: T::cast(2) - T::cast(2) * radius,
+            next: None,
+            up,
+            top: up && radius > T::zero(),
+        };
+
+        rows.next = rows.walk();
+        rows
+    }
+
+    /// Get the next point of the first quadrant, from `(radius, 0)` towards `(0, radius)`.
+    #[inline]
+    fn walk(&mut self) -> Option<Point<T>> {
+        if self.x >= T::zero() {
+            return None;
+        }
+
+        let point = (-self.x, self.y);
+        let error = self.error;
+
+        if error <= self.y {
+            self.y += T::one();
+            self.error += self.y * T::cast(2) + T::one();
+        }
+
+        if error > self.x || self.error > self.y {
+            self.x += T::one();
+            self.error += self.x * T::cast(2) + T::one();
+        }
+
+        Some(point)
+    }
+}
+
+impl<T: SignedNum> Iterator for Rows<T> {
+    type Item = (T, T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.next {
+            Some(first) => first,
+            // The point at the top of the circle belongs to the next quadrant
+            None if self.top => {
+                self.top = false;
+                return Some((self.radius, T::zero(), T::zero()));
+            }
+            None => return None,
+        };
+
+        let up = self.up;
+        let flip = |point: Point<T>| if up { (point.1, point.0) } else { point };
+        let (row, mut low) = flip(first);
+        let mut high = low;
+
+        loop {
+            self.next = self.walk();
+
+            match self.next.map(flip) {
+                Some((next_row, x)) if next_row == row => {
+                    low = min(low, x);
+                    high = max(high, x);
+                }
+                _ => break,
+            }
+        }
+
+        if self.up && row == self.radius {
+            self.top = false;
+            low = T::zero();
+        }
+
+        Some((row, low, high))
+    }
+}
+
+/// The rows of a whole circle, going up from the bottom to the middle and then on to the top.
+///
+/// Each row is returned as `(row, low, high)` like `Rows`, with the row as an offset from the
+/// centre.
+struct Halves<T> {
+    radius: T,
+    rows: Rows<T>,
+}
+
+impl<T: SignedNum> Halves<T> {
+    #[inline]
+    fn new(radius: T) -> Self {
+        Self {
+            radius,
+            rows: Rows::new(radius, false),
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for Halves<T> {
+    type Item = (T, T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.rows.next() {
+                Some((row, low, high)) if self.rows.up => return Some((row, low, high)),
+                Some((row, low, high)) => return Some((-row, low, high)),
+                None if self.rows.up => return None,
+                None => self.rows = Rows::new(self.radius, true),
+            }
+        }
+    }
+}
+
+/// Count the points from `low` to `high`, including both of them.
+#[inline]
+fn length<T: SignedNum>(low: T, high: T) -> usize {
+    (high - low).to_usize().unwrap() + 1
+}
+
+/// A filled circle, returned as horizontal `(start, length)` spans.
+///
+/// Each row ends exactly on the points of the [`BresenhamCircle`] with the same centre and radius,
+/// so filling the circle covers all of its outline, and drawing the outline on top of it doesn't
+/// leave any gaps or stick out past the fill. Like [`BresenhamCircle`], a circle with a radius of
+/// zero has no points.
+///
+/// The spans are returned a row at a time in order of increasing y, one span for each row. A span
+/// covers `start.0..start.0 + length` on row `start.1`. The rows are found by walking a quarter of
+/// the circle, so no points are tested and each span takes constant time on average.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::FilledCircle;
+///
+/// fn main() {
+///     for (start, length) in FilledCircle::new(0, 0, 2) {
+///         println!("{:?} {}", start, length);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (-1, -2) 3
+/// (-2, -1) 5
+/// (-2, 0) 5
+/// (-2, 1) 5
+/// (-1, 2) 3
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+pub struct FilledCircle<T> {
+    center: Point<T>,
+    rows: Halves<T>,
+}
+
+impl<T: SignedNum> FilledCircle<T> {
+    #[inline]
+    pub fn new(center_x: T, center_y: T, radius: T) -> Self {
+        Self {
+            center: (center_x, center_y),
+            rows: Halves::new(radius),
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for FilledCircle<T> {
+    type Item = (Point<T>, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (row, _, high) = self.rows.next()?;
+
+        Some((
+            (self.center.0 - high, self.center.1 + row),
+            length(-high, high),
+        ))
+    }
+}
+
+/// A filled ring between two circles, returned as horizontal `(start, length)` spans.
+///
+/// The ring covers the points of both the inner and outer [`BresenhamCircle`] and everything
+/// between them, so like [`FilledCircle`], each span ends exactly on the outlines of the circles.
+/// If the inner radius is zero, there's no hole and the ring is the same as a [`FilledCircle`].
+/// The radii can be given either way around.
+///
+/// The spans are returned a row at a time in order of increasing y, and from left to right within
+/// each row. Rows that go through the hole have two spans, and the others have one.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Annulus;
+///
+/// fn main() {
+///     for (start, length) in Annulus::new(0, 0, 2, 3) {
+///         println!("{:?} {}", start, length);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (-1, -3) 3
+/// (-2, -2) 5
+/// (-3, -1) 2
+/// (2, -1) 2
+/// (-3, 0) 2
+/// (2, 0) 2
+/// (-3, 1) 2
+/// (2, 1) 2
+/// (-2, 2) 5
+/// (-1, 3) 3
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+/// [`FilledCircle`]: struct.FilledCircle.html
+pub struct Annulus<T> {
+    center: Point<T>,
+    inner_radius: T,
+    outer: Halves<T>,
+    inner: Halves<T>,
+    right: Option<(Point<T>, usize)>,
+}
+
+impl<T: SignedNum> Annulus<T> {
+    #[inline]
+    pub fn new(center_x: T, center_y: T, inner_radius: T, outer_radius: T) -> Self {
+        let (inner_radius, outer_radius) = (
+            min(inner_radius, outer_radius),
+            max(inner_radius, outer_radius),
+        );
+
+        Self {
+            center: (center_x, center_y),
+            inner_radius,
+            outer: Halves::new(outer_radius),
+            inner: Halves::new(inner_radius),
+            right: None,
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for Annulus<T> {
+    type Item = (Point<T>, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(right) = self.right.take() {
+            return Some(right);
+        }
+
+        let (row, _, high) = self.outer.next()?;
+        let y = self.center.1 + row;
+
+        // The inner circle's rows come in the same order, once the outer rows reach them
+        let hole = if row.abs() <= self.inner_radius {
+            self.inner.next().map_or(T::zero(), |(_, low, _)| low)
+        } else {
+            T::zero()
+        };
+
+        if hole > T::zero() {
+            self.right = Some(((self.center.0 + hole, y), length(hole, high)));
+            Some(((self.center.0 - high, y), length(hole, high)))
+        } else {
+            Some(((self.center.0 - high, y), length(-high, high)))
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use BresenhamCircle;
+
+    let filled = |radius| FilledCircle::new(0, 0, radius).collect::<Vec<_>>();
+
+    assert_eq!(filled(1), [((0, -1), 1), ((-1, 0), 3), ((0, 1), 1)]);
+    assert_eq!(filled(0), []);
+
+    // The ends of each span are on the outline
+    for radius in 1..20 {
+        let spans = filled(radius);
+
+        for point in BresenhamCircle::new(0, 0, radius) {
+            assert!(spans.iter().any(|&((x, y), length)| {
+                y == point.1 && x <= point.0 && point.0 < x + length as i32
+            }));
+        }
+
+        for &((x, y), length) in &spans {
+            let end = (x + length as i32 - 1, y);
+            assert!(BresenhamCircle::new(0, 0, radius).any(|point| point == (x, y)));
+            assert!(BresenhamCircle::new(0, 0, radius).any(|point| point == end));
+        }
+    }
+
+    // Without a hole, a ring is a filled circle
+    assert!(Annulus::new(3, 4, 0, 5).eq(FilledCircle::new(3, 4, 5)));
+
+    // With two circles the same size, it's just the outline
+    let ring = Annulus::new(0, 0, 4, 4)
+        .map(|(_, length)| length)
+        .sum::<usize>();
+    assert_eq!(ring, BresenhamCircle::new(0, 0, 4).count());
+}
@@ -6,6 +6,9 @@ use self::rand::Rng;
 use self::rand::distributions::uniform::SampleUniform;
 use ::*;
 
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fmt::Debug;
 use std::ops::Neg;
 
 const NUM_TESTS: u16 = 10000;
@@ -34,6 +37,273 @@ where
     )
 }
 
+fn assert_double_ended<I, F>(make: F)
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    I::Item: PartialEq + Debug + Clone,
+    F: Fn() -> I,
+{
+    let forward = make().collect::<Vec<_>>();
+
+    assert_eq!(make().len(), forward.len());
+    assert_eq!(make().rev().collect::<Vec<_>>(), reverse_slice(&forward));
+
+    // Take points alternately from either end until they meet in the middle
+    let mut iter = make();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+
+    loop {
+        assert_eq!(iter.len(), forward.len() - front.len() - back.len());
+
+        match iter.next() {
+            Some(point) => front.push(point),
+            None => break,
+        }
+
+        match iter.next_back() {
+            Some(point) => back.push(point),
+            None => break,
+        }
+    }
+
+    front.extend(back.into_iter().rev());
+    assert_eq!(front, forward);
+}
+
+#[test]
+fn double_ended() {
+    use VoxelOrigin::{Center, Corner};
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        assert_double_ended(|| Bresenham::new(start, end));
+        assert_double_ended(|| Bresenham::new(start, end).steps());
+        assert_double_ended(|| SymmetricBresenham::new(start, end));
+        assert_double_ended(|| WalkGrid::new(start, end));
+        assert_double_ended(|| Supercover::new(start, end));
+
+        let clip = random_rect(&mut rng, RANGE);
+        assert_double_ended(|| Bresenham::clipped(start, end, clip));
+        assert_double_ended(|| SymmetricBresenham::clipped(start, end, clip));
+
+        let start = random_point(&mut rng, RANGE as f64);
+        let end = random_point(&mut rng, RANGE as f64);
+
+        assert_double_ended(|| Midpoint::<_, i32>::new(start, end));
+
+        let start = random_point(&mut rng, RANGE_FLOAT * 2.0);
+        let end = random_point(&mut rng, RANGE_FLOAT * 2.0);
+
+        assert_double_ended(|| Midpoint::<_, i32>::new(start, end));
+
+        let start = random_voxel(&mut rng, RANGE);
+        let end = random_voxel(&mut rng, RANGE);
+
+        assert_double_ended(|| Bresenham3d::new(start, end));
+        assert_double_ended(|| SymmetricBresenham3d::new(start, end));
+
+        let start = random_voxel(&mut rng, RANGE_FLOAT);
+        let end = random_voxel(&mut rng, RANGE_FLOAT);
+
+        assert_double_ended(|| WalkVoxels::<_, i32>::new(start, end, &Center));
+        assert_double_ended(|| WalkVoxels::<_, i32>::new(start, end, &Corner));
+    }
+}
+
+fn assert_random_access<I, F, P>(make: F, point_at: P)
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    I::Item: PartialEq + Debug + Clone,
+    F: Fn() -> I,
+    P: Fn(&I, usize) -> Option<I::Item>,
+{
+    let forward = make().collect::<Vec<_>>();
+    let line = make();
+
+    for index in 0..forward.len() + 2 {
+        assert_eq!(point_at(&line, index), forward.get(index).cloned());
+        assert_eq!(make().nth(index), forward.get(index).cloned());
+        assert_eq!(
+            make().nth_back(index),
+            forward.iter().rev().nth(index).cloned()
+        );
+    }
+
+    // Jumping ahead should leave the line ready to carry on from there
+    let mut rng = rand::thread_rng();
+    let skip = rng.gen_range(0..forward.len() + 1);
+    let skip_back = rng.gen_range(0..forward.len() + 1);
+
+    let mut line = make();
+    line.nth(skip);
+    line.nth_back(skip_back);
+
+    let end = forward.len().saturating_sub(skip_back + 1);
+    let expected = if skip + 1 < end {
+        &forward[skip + 1..end]
+    } else {
+        &[]
+    };
+    assert_eq!(line.collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn random_access() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        assert_random_access(
+            || Bresenham::new(start, end),
+            |line, index| line.point_at(index as isize),
+        );
+
+        assert_random_access(
+            || SymmetricBresenham::new(start, end),
+            |line, index| line.point_at(index as isize),
+        );
+
+        let start = random_point(&mut rng, RANGE as f64);
+        let end = random_point(&mut rng, RANGE as f64);
+
+        assert_random_access(
+            || Midpoint::<_, i32>::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+
+        let start = random_point(&mut rng, RANGE_FLOAT * 2.0);
+        let end = random_point(&mut rng, RANGE_FLOAT * 2.0);
+
+        assert_random_access(
+            || Midpoint::<_, i32>::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+
+        let start = random_voxel(&mut rng, RANGE);
+        let end = random_voxel(&mut rng, RANGE);
+
+        assert_random_access(
+            || Bresenham3d::new(start, end),
+            |line, index| line.point_at(index as isize),
+        );
+
+        assert_random_access(
+            || SymmetricBresenham3d::new(start, end),
+            |line, index| line.point_at(index as isize),
+        );
+    }
+}
+
+/// Check a sample of the points of a long line against walking it from the start.
+fn assert_sampled_random_access<I, F, P>(make: F, point_at: P)
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    I::Item: PartialEq + Debug + Clone,
+    F: Fn() -> I,
+    P: Fn(&I, usize) -> Option<I::Item>,
+{
+    let forward = make().collect::<Vec<_>>();
+    let line = make();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..100 {
+        let index = rng.gen_range(0..forward.len());
+        assert_eq!(point_at(&line, index), Some(forward[index].clone()));
+        assert_eq!(make().nth(index), Some(forward[index].clone()));
+    }
+}
+
+#[test]
+fn random_access_on_long_lines() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 1000 {
+        // Multiplying an index by the length of these lines overflows `i32`
+        let start = random_point(&mut rng, 100000i32);
+        let end = random_point(&mut rng, 100000i32);
+
+        assert_sampled_random_access(
+            || Bresenham::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+
+        assert_sampled_random_access(
+            || SymmetricBresenham::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+
+        let start = random_voxel(&mut rng, 100000i32);
+        let end = random_voxel(&mut rng, 100000i32);
+
+        assert_sampled_random_access(
+            || Bresenham3d::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+
+        assert_sampled_random_access(
+            || SymmetricBresenham3d::new(start, end),
+            |line, index| line.point_at(index as i32),
+        );
+    }
+}
+
+/// The original floating-point versions of `WalkGrid` and `Supercover`, which are exact for
+/// short lines.
+fn float_grid_walk(start: Point<isize>, end: Point<isize>, supercover: bool) -> Vec<Point<isize>> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let (nx, ny) = (dx.abs() as f32, dy.abs() as f32);
+    let (mut ix, mut iy) = (0.0, 0.0);
+    let mut point = start;
+    let mut points = Vec::new();
+
+    while ix <= nx && iy <= ny {
+        points.push(point);
+
+        let comparison = ((0.5 + ix) / nx) - ((0.5 + iy) / ny);
+
+        if supercover && comparison == 0.0 {
+            point.0 += dx.signum();
+            point.1 += dy.signum();
+            ix += 1.0;
+            iy += 1.0;
+        } else if comparison < 0.0 {
+            point.0 += dx.signum();
+            ix += 1.0;
+        } else {
+            point.1 += dy.signum();
+            iy += 1.0;
+        }
+    }
+
+    points
+}
+
+#[test]
+fn grid_walks_match_floats() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        assert_eq!(
+            WalkGrid::new(start, end).collect::<Vec<_>>(),
+            float_grid_walk(start, end, false)
+        );
+        assert_eq!(
+            Supercover::new(start, end).collect::<Vec<_>>(),
+            float_grid_walk(start, end, true)
+        );
+    }
+}
+
 #[test]
 fn supercover_symmetrical() {
     let supercover = |a, b| Supercover::new(a, b).collect::<Vec<_>>();
@@ -51,6 +321,216 @@ fn supercover_symmetrical() {
     }
 }
 
+fn random_rect<T>(rng: &mut rand::rngs::ThreadRng, range: T) -> Rect<T>
+where
+    T: SampleUniform + PartialOrd + Neg<Output = T> + Copy,
+{
+    let a = random_point(rng, range);
+    let b = random_point(rng, range);
+
+    (
+        (
+            if a.0 < b.0 { a.0 } else { b.0 },
+            if a.1 < b.1 { a.1 } else { b.1 },
+        ),
+        (
+            if a.0 < b.0 { b.0 } else { a.0 },
+            if a.1 < b.1 { b.1 } else { a.1 },
+        ),
+    )
+}
+
+fn inside<T: PartialOrd>(point: &Point<T>, rect: &Rect<T>) -> bool {
+    point.0 >= (rect.0).0 && point.0 <= (rect.1).0 && point.1 >= (rect.0).1 && point.1 <= (rect.1).1
+}
+
+#[test]
+fn bresenham_clipped_matches_filtered() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let clip = random_rect(&mut rng, RANGE);
+
+        assert_eq!(
+            Bresenham::clipped(start, end, clip).collect::<Vec<_>>(),
+            Bresenham::new(start, end)
+                .filter(|point| inside(point, &clip))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn symmetric_bresenham_clipped_matches_filtered() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let clip = random_rect(&mut rng, RANGE);
+
+        assert_eq!(
+            SymmetricBresenham::clipped(start, end, clip).collect::<Vec<_>>(),
+            SymmetricBresenham::new(start, end)
+                .filter(|point| inside(point, &clip))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn bresenham_clipped_with_far_endpoints() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        let screen = ((0, 0), (639, 479));
+
+        // Multiplying across the length of these lines overflows the type of the points
+        let start = random_point(&mut rng, 16000i16);
+        let end = random_point(&mut rng, 16000i16);
+
+        assert_eq!(
+            Bresenham::clipped(start, end, screen).collect::<Vec<_>>(),
+            Bresenham::new(start, end)
+                .filter(|point| inside(point, &screen))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            SymmetricBresenham::clipped(start, end, screen).collect::<Vec<_>>(),
+            SymmetricBresenham::new(start, end)
+                .filter(|point| inside(point, &screen))
+                .collect::<Vec<_>>()
+        );
+
+        let start = random_point(&mut rng, 100000i32);
+        let end = random_point(&mut rng, 100000i32);
+        let screen = ((0, 0), (639, 479));
+
+        assert_eq!(
+            Bresenham::clipped(start, end, screen).collect::<Vec<_>>(),
+            Bresenham::new(start, end)
+                .filter(|point| inside(point, &screen))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn midpoint_clipped_matches_filtered() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE as f64);
+        let end = random_point(&mut rng, RANGE as f64);
+        let clip = random_rect(&mut rng, RANGE as i32);
+
+        assert_eq!(
+            Midpoint::<f64, i32>::clipped(start, end, clip).collect::<Vec<_>>(),
+            Midpoint::<f64, i32>::new(start, end)
+                .filter(|point| inside(point, &clip))
+                .collect::<Vec<_>>()
+        );
+
+        // Low-precision floats round the same way no matter where the line is entered
+        let start = random_point(&mut rng, RANGE_FLOAT * 2.0);
+        let end = random_point(&mut rng, RANGE_FLOAT * 2.0);
+        let clip = random_rect(&mut rng, RANGE as i32 * 2);
+
+        assert_eq!(
+            Midpoint::<f32, i32>::clipped(start, end, clip).collect::<Vec<_>>(),
+            Midpoint::<f32, i32>::new(start, end)
+                .filter(|point| inside(point, &clip))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+/// The original version of `Midpoint`, which adds up the decision variable one step at a time.
+fn original_midpoint(start: Point<f32>, end: Point<f32>) -> Vec<Point<i32>> {
+    let octant = octant::Octant::new(start, end);
+    let (start, end) = (octant.to(start), octant.to(end));
+
+    let a = -(end.1 - start.1);
+    let b = end.0 - start.0;
+    let c = start.0 * end.1 - end.0 * start.1;
+
+    let mut point = (start.0.round() as i32, start.1.round() as i32);
+    let mut k = a * (start.0.round() + 1.0) + b * (start.1.round() + 0.5) + c;
+    let mut points = Vec::new();
+
+    while point.0 <= end.0.round() as i32 {
+        points.push(octant.from(point));
+
+        if k <= 0.0 {
+            k += b;
+            point.1 += 1;
+        }
+
+        k += a;
+        point.0 += 1;
+    }
+
+    points
+}
+
+#[test]
+fn midpoint_matches_original() {
+    let mut rng = rand::thread_rng();
+
+    // A line where rounding in the running sum changes which points are drawn
+    let (start, end) = ((389.143, -326.85327), (-192.8085, 264.08496));
+    assert_eq!(
+        Midpoint::<f32, i32>::new(start, end).collect::<Vec<_>>(),
+        original_midpoint(start, end)
+    );
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE_FLOAT * 4.0);
+        let end = random_point(&mut rng, RANGE_FLOAT * 4.0);
+
+        assert_eq!(
+            Midpoint::<f32, i32>::new(start, end).collect::<Vec<_>>(),
+            original_midpoint(start, end)
+        );
+    }
+}
+
+#[test]
+fn symmetric_bresenham_symmetrical() {
+    let symmetric = |a, b| SymmetricBresenham::new(a, b).collect::<Vec<_>>();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+
+        assert_eq!(symmetric(start, end), reverse_slice(&symmetric(end, start)));
+        assert_eq!(
+            SymmetricBresenham::new(start, end).len(),
+            Bresenham::new(start, end).len()
+        );
+    }
+}
+
+#[test]
+fn symmetric_bresenham_3d_symmetrical() {
+    let symmetric = |a, b| SymmetricBresenham3d::new(a, b).collect::<Vec<_>>();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_voxel(&mut rng, RANGE);
+        let end = random_voxel(&mut rng, RANGE);
+
+        assert_eq!(symmetric(start, end), reverse_slice(&symmetric(end, start)));
+        assert_eq!(
+            SymmetricBresenham3d::new(start, end).len(),
+            Bresenham3d::new(start, end).len()
+        );
+    }
+}
+
 #[test]
 #[should_panic]
 fn bresenham_not_symmetrical() {
@@ -100,3 +580,969 @@ fn walk_voxels_symmetrical() {
         );
     }
 }
+
+/// The original version of `WalkVoxels` with centred voxels, which adds up the error terms one
+/// step at a time.
+fn original_walk_voxels(start: Voxel<f32>, end: Voxel<f32>) -> Vec<Voxel<i32>> {
+    let round = |(x, y, z): Voxel<f32>| (x.round() as i32, y.round() as i32, z.round() as i32);
+    let (start_i, end_i) = (round(start), round(end));
+    let count =
+        (start_i.0 - end_i.0).abs() + (start_i.1 - end_i.1).abs() + (start_i.2 - end_i.2).abs();
+    let sign = (
+        (end_i.0 - start_i.0).signum(),
+        (end_i.1 - start_i.1).signum(),
+        (end_i.2 - start_i.2).signum(),
+    );
+    let plane = |start: i32, end: i32| (if end > start { start + 1 } else { start }) as f32;
+    let scale = |start: f32, end: f32| if start == end { 1.0 } else { end - start };
+    let (vx, vy, vz) = (
+        scale(start.0, end.0),
+        scale(start.1, end.1),
+        scale(start.2, end.2),
+    );
+
+    let mut err = (
+        (plane(start_i.0, end_i.0) - start.0) * (vy * vz),
+        (plane(start_i.1, end_i.1) - start.1) * (vx * vz),
+        (plane(start_i.2, end_i.2) - start.2) * (vx * vy),
+    );
+    let d_err = (
+        sign.0 as f32 * (vy * vz),
+        sign.1 as f32 * (vx * vz),
+        sign.2 as f32 * (vx * vy),
+    );
+    let mut voxel = start_i;
+    let mut voxels = Vec::new();
+
+    for _ in 0..=count {
+        voxels.push(voxel);
+
+        let (xr, yr, zr) = (err.0.abs(), err.1.abs(), err.2.abs());
+        let (x_zero, y_zero, z_zero) = (sign.0 == 0, sign.1 == 0, sign.2 == 0);
+
+        if !x_zero && (y_zero || xr < yr) && (z_zero || xr < zr) {
+            voxel.0 += sign.0;
+            err.0 += d_err.0;
+        } else if !y_zero && (z_zero || yr < zr) {
+            voxel.1 += sign.1;
+            err.1 += d_err.1;
+        } else if !z_zero {
+            voxel.2 += sign.2;
+            err.2 += d_err.2;
+        }
+    }
+
+    voxels
+}
+
+#[test]
+fn walk_voxels_matches_original() {
+    use VoxelOrigin::Center;
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_voxel(&mut rng, RANGE_FLOAT * 2.0);
+        let end = random_voxel(&mut rng, RANGE_FLOAT * 2.0);
+
+        assert_eq!(
+            WalkVoxels::<f32, i32>::new(start, end, &Center).collect::<Vec<_>>(),
+            original_walk_voxels(start, end)
+        );
+    }
+}
+
+/// Check whether the centre of `point` lies inside a thick line.
+fn thick_line_contains(
+    start: Point<isize>,
+    end: Point<isize>,
+    width: isize,
+    measure: &WidthMeasure,
+    point: Point<isize>,
+) -> bool {
+    let octant = octant::Octant::new(start, end);
+    let (start, end, point) = (octant.to(start), octant.to(end), octant.to(point));
+    let (length_x, length_y) = (end.0 - start.0, end.1 - start.1);
+    let (dx, dy) = if start == end {
+        (1, 0)
+    } else {
+        (length_x, length_y)
+    };
+    let (x, y) = (point.0 - start.0, point.1 - start.1);
+    let t = 2 * (dx * y - dy * x);
+    let p = dx * x + dy * y;
+
+    match *measure {
+        WidthMeasure::Perpendicular => {
+            let squared = (width * width) as i128 * (dx * dx + dy * dy) as i128;
+            let t = t as i128;
+
+            (t <= 0 || t * t <= squared)
+                && (t > 0 || t * t < squared)
+                && p >= 0
+                && p <= length_x * dx + length_y * dy
+        }
+        WidthMeasure::Axis => t <= width * dx && t > -width * dx && x >= 0 && x <= length_x,
+    }
+}
+
+/// Every pixel whose centre lies inside a thick line, found by checking a box around it.
+fn brute_force_thick_line(
+    start: Point<isize>,
+    end: Point<isize>,
+    width: isize,
+    measure: &WidthMeasure,
+) -> Vec<Point<isize>> {
+    let mut points = Vec::new();
+
+    for x in min(start.0, end.0) - width - 1..=max(start.0, end.0) + width + 1 {
+        for y in min(start.1, end.1) - width - 1..=max(start.1, end.1) + width + 1 {
+            if thick_line_contains(start, end, width, measure, (x, y)) {
+                points.push((x, y));
+            }
+        }
+    }
+
+    points.sort();
+    points
+}
+
+#[test]
+fn thick_line_matches_brute_force() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, 20);
+        let end = random_point(&mut rng, 20);
+        let width = rng.gen_range(0..8);
+
+        for measure in &[WidthMeasure::Perpendicular, WidthMeasure::Axis] {
+            let mut points = ThickLine::new(start, end, width, measure).collect::<Vec<_>>();
+            points.sort();
+
+            assert_eq!(points, brute_force_thick_line(start, end, width, measure));
+
+            // Wide enough lines always cover the thin one
+            if width >= 2 {
+                for point in Bresenham::new(start, end) {
+                    assert!(points.binary_search(&point).is_ok());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn thick_line_with_screen_coordinates() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        // Squaring the width times the length of these overflows `i32`
+        let start = (rng.gen_range(0..2560), rng.gen_range(0..1440));
+        let end = (rng.gen_range(0..2560), rng.gen_range(0..1440));
+        let width = rng.gen_range(0..50);
+        let wide = |(x, y): Point<i32>| (x as isize, y as isize);
+
+        for measure in &[WidthMeasure::Perpendicular, WidthMeasure::Axis] {
+            let mut points = ThickLine::<i32>::new(start, end, width, measure)
+                .map(wide)
+                .collect::<Vec<_>>();
+
+            // Even the length of these lines times their direction overflows `i16`
+            let narrow = |(x, y): Point<i32>| (x as i16, y as i16);
+            assert_eq!(
+                ThickLine::<i16>::new(narrow(start), narrow(end), width as i16, measure)
+                    .map(|(x, y)| (x as isize, y as isize))
+                    .collect::<Vec<_>>(),
+                points
+            );
+            points.sort();
+            let count = points.len();
+            points.dedup();
+            assert_eq!(points.len(), count);
+
+            let (start, end) = (wide(start), wide(end));
+            let width = width as isize;
+
+            for &point in &points {
+                assert!(thick_line_contains(start, end, width, measure, point));
+            }
+
+            // Check a sample of the pixels around the line instead of all of them
+            let low = (
+                min(start.0, end.0) - width - 1,
+                min(start.1, end.1) - width - 1,
+            );
+            let high = (
+                max(start.0, end.0) + width + 1,
+                max(start.1, end.1) + width + 1,
+            );
+
+            for _ in 0..1000 {
+                let point = (rng.gen_range(low.0..=high.0), rng.gen_range(low.1..=high.1));
+
+                assert_eq!(
+                    points.binary_search(&point).is_ok(),
+                    thick_line_contains(start, end, width, measure, point)
+                );
+            }
+        }
+    }
+}
+
+/// Expand runs back into the points they cover, in the order of the line.
+fn expand_spans<I>(spans: I, line: &[Point<isize>]) -> Vec<Point<isize>>
+where
+    I: Iterator<Item = (Point<isize>, usize, spans::Axis)>,
+{
+    let mut points = Vec::new();
+
+    for (start, length, axis) in spans {
+        let mut run = (0..length as isize)
+            .map(|i| match axis {
+                spans::Axis::X => (start.0 + i, start.1),
+                spans::Axis::Y => (start.0, start.1 + i),
+            })
+            .collect::<Vec<_>>();
+
+        // Runs always start from their smallest point, so flip them to match the line
+        if points.len() < line.len() && run[0] != line[points.len()] {
+            run.reverse();
+        }
+
+        points.extend(run);
+    }
+
+    points
+}
+
+#[test]
+fn spans_match_points() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let clip = random_rect(&mut rng, RANGE);
+
+        let points = Bresenham::new(start, end).collect::<Vec<_>>();
+        assert_eq!(
+            expand_spans(Bresenham::new(start, end).spans(), &points),
+            points
+        );
+
+        let points = SymmetricBresenham::clipped(start, end, clip).collect::<Vec<_>>();
+        assert_eq!(
+            expand_spans(
+                SymmetricBresenham::clipped(start, end, clip).spans(),
+                &points
+            ),
+            points
+        );
+
+        let (start, end) = (
+            (start.0 as f64 / 3.0, start.1 as f64 / 3.0),
+            (end.0 as f64 / 3.0, end.1 as f64 / 3.0),
+        );
+        let points = Midpoint::<_, isize>::new(start, end).collect::<Vec<_>>();
+        assert_eq!(
+            expand_spans(Midpoint::new(start, end).spans(), &points),
+            points
+        );
+    }
+}
+
+#[test]
+fn dashes_carry_across_segments() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, RANGE);
+        let end = random_point(&mut rng, RANGE);
+        let lengths = [rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0), 5.0];
+        let points = Bresenham::new(start, end).collect::<Vec<_>>();
+        let split = rng.gen_range(0..points.len());
+
+        for &measure in &[DashMeasure::Steps, DashMeasure::Length] {
+            let mut whole = DashPattern::new(&lengths, measure);
+            let mut split_up = DashPattern::new(&lengths, measure);
+
+            let mut dashed = split_up
+                .dash(points[..split].iter().cloned())
+                .collect::<Vec<_>>();
+            dashed.extend(split_up.dash(points[split..].iter().cloned()));
+
+            assert_eq!(
+                whole.dash(points.iter().cloned()).collect::<Vec<_>>(),
+                dashed
+            );
+        }
+    }
+}
+
+/// Join up the lines through `vertices`, dropping the first point of each line after the first.
+fn chained_lines(vertices: &[Point<isize>], algorithm: LineAlgorithm) -> Vec<Point<isize>> {
+    let mut points = vec![vertices[0]];
+
+    for pair in vertices.windows(2) {
+        let line: Vec<_> = match algorithm {
+            LineAlgorithm::Bresenham => Bresenham::new(pair[0], pair[1]).collect(),
+            LineAlgorithm::Supercover => Supercover::new(pair[0], pair[1]).collect(),
+            LineAlgorithm::WalkGrid => WalkGrid::new(pair[0], pair[1]).collect(),
+        };
+        points.extend(line.into_iter().skip(1));
+    }
+
+    points
+}
+
+#[test]
+fn polylines_join_up() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let count = rng.gen_range(1..6);
+        let mut vertices = (0..count)
+            .map(|_| random_point(&mut rng, 20))
+            .collect::<Vec<_>>();
+
+        for &algorithm in &[
+            LineAlgorithm::Bresenham,
+            LineAlgorithm::Supercover,
+            LineAlgorithm::WalkGrid,
+        ] {
+            assert_eq!(
+                Polyline::new(&vertices, &algorithm).collect::<Vec<_>>(),
+                chained_lines(&vertices, algorithm)
+            );
+
+            let outline = PolygonOutline::new(&vertices, &algorithm).collect::<Vec<_>>();
+            vertices.push(vertices[0]);
+            let mut closed = chained_lines(&vertices, algorithm);
+            vertices.pop();
+
+            if closed.len() > 1 {
+                closed.pop();
+            }
+
+            assert_eq!(outline, closed);
+        }
+    }
+}
+
+#[test]
+fn bresenham_rows_match_points() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, 50);
+        let end = random_point(&mut rng, 50);
+        let line = Bresenham::new(start, end);
+        let points = Bresenham::new(start, end).collect::<Vec<_>>();
+
+        for y in -52..52 {
+            let row = points
+                .iter()
+                .filter(|point| point.1 == y)
+                .map(|point| point.0);
+            let expected = match (row.clone().min(), row.max()) {
+                (Some(low), Some(high)) => Some((low, high)),
+                _ => None,
+            };
+
+            assert_eq!(line.row_at(y), expected);
+        }
+    }
+}
+
+/// Check if the centre of a pixel is inside a polygon, using exact comparisons.
+fn brute_force_inside(vertices: &[Point<isize>], point: Point<isize>, rule: FillRule) -> bool {
+    let (x, y) = point;
+    let mut crossings = 0;
+    let mut winding = 0;
+
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+
+        // Does the edge cross the row to the left of the point?
+        let (ahead, across) = ((x - a.0) * (b.1 - a.1), (y - a.1) * (b.0 - a.0));
+
+        if a.1 <= y && y < b.1 && ahead > across {
+            crossings += 1;
+            winding += 1;
+        } else if b.1 <= y && y < a.1 && ahead < across {
+            crossings += 1;
+            winding -= 1;
+        }
+    }
+
+    match rule {
+        FillRule::EvenOdd => crossings % 2 == 1,
+        FillRule::NonZero => winding != 0,
+    }
+}
+
+#[test]
+fn polygon_fill_matches_brute_force() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let count = rng.gen_range(1..7);
+        let vertices = (0..count)
+            .map(|_| random_point(&mut rng, 15))
+            .collect::<Vec<_>>();
+        let outline = PolygonOutline::new(&vertices, &LineAlgorithm::Bresenham).collect::<Vec<_>>();
+
+        for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+            let mut filled = Vec::new();
+            let mut last: Option<Point<isize>> = None;
+
+            for ((x, y), length) in PolygonFill::new(&vertices, rule) {
+                // Spans are in order, and never overlap or touch
+                assert!(length > 0);
+                assert!(last.is_none() || last < Some((y, x - 1)));
+                last = Some((y, x + length as isize - 1));
+
+                filled.extend((x..x + length as isize).map(|x| (x, y)));
+            }
+
+            let mut expected = Vec::new();
+            for y in -16..16 {
+                for x in -16..16 {
+                    if outline.contains(&(x, y)) || brute_force_inside(&vertices, (x, y), rule) {
+                        expected.push((x, y));
+                    }
+                }
+            }
+
+            filled.sort_by_key(|&(x, y)| (y, x));
+            assert_eq!(filled, expected);
+        }
+    }
+}
+
+#[test]
+fn gupta_sproull_brightness_is_even() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let start = random_point(&mut rng, 500.0);
+        let end = random_point(&mut rng, 500.0);
+        let length = (end.0 - start.0).hypot(end.1 - start.1);
+
+        if length < 200.0 {
+            continue;
+        }
+
+        // A line one pixel wide should have about as much brightness as its length
+        let total: f64 = GuptaSproull::<_, i32>::new(start, end)
+            .map(|(_, value)| value)
+            .sum();
+
+        assert!((total / length - 1.0).abs() < 0.05);
+    }
+}
+
+#[test]
+fn xiaolin_wu_ends_are_fractional() {
+    use std::collections::HashMap;
+
+    let mut rng = rand::thread_rng();
+
+    let coverage = |start, end| {
+        let mut coverage = HashMap::new();
+
+        for (point, value) in XiaolinWu::<f64, isize>::new(start, end) {
+            *coverage.entry(point).or_insert(0.0) += value;
+        }
+
+        coverage.retain(|_, value| *value > 1e-6);
+        coverage
+    };
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, 50.0);
+        let end = random_point(&mut rng, 50.0);
+
+        // Each step along the major axis has a total coverage of one
+        let length = (end.0 - start.0).abs().max((end.1 - start.1).abs());
+        let total: f64 = XiaolinWu::<_, isize>::new(start, end)
+            .map(|(_, value)| value)
+            .sum();
+        assert!((total - length).abs() < 1e-6);
+
+        // Drawing the line backwards covers the same points
+        let forwards = coverage(start, end);
+        let backwards = coverage(end, start);
+        assert_eq!(forwards.len(), backwards.len());
+
+        for (point, value) in forwards {
+            assert!((backwards[&point] - value).abs() < 1e-6);
+        }
+
+        // And the first point is at the start
+        if let Some(((x, y), _)) = XiaolinWu::<_, isize>::new(start, end).next() {
+            assert!((x as f64 - start.0).abs() < 1.5 && (y as f64 - start.1).abs() < 1.5);
+        }
+    }
+}
+
+#[test]
+fn antialiased_thick_line_covers_its_area() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS {
+        let start = random_point(&mut rng, 50.0);
+        let end = random_point(&mut rng, 50.0);
+        let width = rng.gen_range(0.0..6.0);
+        let length = (end.0 - start.0).hypot(end.1 - start.1);
+
+        for &(cap, extend) in &[(LineCap::Butt, 0.0), (LineCap::Square, width)] {
+            let mut total = 0.0;
+            let mut last = None;
+
+            for ((x, y), value) in AntialiasedThickLine::<f64, isize>::new(start, end, width, cap) {
+                // Each pixel is only returned once, in order
+                assert!(last.is_none() || last < Some((y, x)));
+                assert!(value > 0.0 && value <= 1.0);
+                last = Some((y, x));
+                total += value;
+            }
+
+            assert!((total - width * (length + extend)).abs() < 1e-6);
+        }
+    }
+}
+
+#[test]
+fn xiaolin_wu_circle_has_no_seams() {
+    let mut rng = rand::thread_rng();
+
+    // How much a pixel is covered, measured along the y axis if `columns` is true, or else x
+    let coverage = |offset: Point<f64>, radius: f64, columns: bool| {
+        let (x, y) = if columns {
+            offset
+        } else {
+            (offset.1, offset.0)
+        };
+        let edge = (radius * radius - x * x).max(0.0).sqrt();
+        (1.0 - (y.abs() - edge).abs()).max(0.0)
+    };
+
+    for _ in 0..NUM_TESTS {
+        // Half-pixel centres make some pixels land exactly on the diagonals
+        let center = (
+            rng.gen_range(-100..100) as f64 / 2.0,
+            rng.gen_range(-100..100) as f64 / 2.0,
+        );
+        let radius = rng.gen_range(0.0..40.0);
+
+        let mut points =
+            XiaolinWuCircle::<_, isize>::new(center.0, center.1, radius).collect::<Vec<_>>();
+
+        for &((x, y), value) in &points {
+            let offset = (x as f64 - center.0, y as f64 - center.1);
+            assert!(value > 0.0 && value <= 1.0);
+
+            // Pixels on the diagonals get the same coverage either way
+            let columns = offset.0.abs() <= offset.1.abs();
+            assert!((value - coverage(offset, radius, columns)).abs() < 1e-9);
+
+            if offset.0.abs() == offset.1.abs() {
+                assert!((value - coverage(offset, radius, !columns)).abs() < 1e-9);
+            }
+        }
+
+        // Every pixel is only returned once
+        let count = points.len();
+        points.sort_by_key(|&(point, _)| point);
+        points.dedup_by_key(|&mut (point, _)| point);
+        assert_eq!(points.len(), count);
+    }
+}
+
+#[test]
+fn antialiased_polygon_fill_covers_its_area() {
+    let mut rng = rand::thread_rng();
+
+    let fill = |vertices: &[Point<f64>], rule, width| {
+        let mut buffer = [0.0; 64];
+        AntialiasedPolygonFill::<_, isize>::new(vertices, rule, &mut buffer[..width])
+            .collect::<Vec<_>>()
+    };
+
+    for _ in 0..NUM_TESTS / 10 {
+        let triangle = [
+            random_point(&mut rng, 30.0),
+            random_point(&mut rng, 30.0),
+            random_point(&mut rng, 30.0),
+        ];
+        let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+        let area = ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() / 2.0;
+
+        for &rule in &[FillRule::EvenOdd, FillRule::NonZero] {
+            let pixels = fill(&triangle, rule, 64);
+            let total: f64 = pixels.iter().map(|&(_, value)| value).sum();
+            assert!((total - area).abs() < 1e-2);
+            assert!(pixels
+                .iter()
+                .all(|&(_, value)| value > 0.0 && value <= 1.0));
+
+            // Smaller buffers give the same results a piece at a time
+            for &width in &[1, 7] {
+                let pieces = fill(&triangle, rule, width);
+                assert_eq!(pieces.len(), pixels.len());
+
+                for (&(a, x), &(b, y)) in pieces.iter().zip(&pixels) {
+                    assert_eq!(a, b);
+                    assert!((x - y).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
+
+/// Check whether a point is on a stroke around `vertices` with butt caps and a bevel join.
+fn stroke_contains(vertices: &[Point<f64>; 3], width: f64, point: Point<f64>) -> bool {
+    let sub = |a: Point<f64>, b: Point<f64>| (a.0 - b.0, a.1 - b.1);
+    let cross = |a: Point<f64>, b: Point<f64>| a.0 * b.1 - a.1 * b.0;
+
+    let on_segment = |start: Point<f64>, end: Point<f64>| {
+        let (direction, offset) = (sub(end, start), sub(point, start));
+        let length = direction.0.hypot(direction.1);
+        let along = (offset.0 * direction.0 + offset.1 * direction.1) / length;
+        along >= 0.0 && along <= length && cross(direction, offset).abs() / length <= width / 2.0
+    };
+
+    // The bevel fills the gap between the line ends on the outside of the corner
+    let normal = |start: Point<f64>, end: Point<f64>| {
+        let (x, y) = sub(end, start);
+        let scale = width / 2.0 / x.hypot(y);
+        (-y * scale, x * scale)
+    };
+    let (before, after) = (sub(vertices[1], vertices[0]), sub(vertices[2], vertices[1]));
+    let side = if cross(before, after) > 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    let corner = |normal: Point<f64>| {
+        (
+            vertices[1].0 + normal.0 * side,
+            vertices[1].1 + normal.1 * side,
+        )
+    };
+    let triangle = [
+        vertices[1],
+        corner(normal(vertices[0], vertices[1])),
+        corner(normal(vertices[1], vertices[2])),
+    ];
+    let sides = [0, 1, 2].iter().map(|&index| {
+        cross(
+            sub(triangle[(index + 1) % 3], triangle[index]),
+            sub(point, triangle[index]),
+        )
+    });
+    let in_bevel = sides.clone().all(|side| side >= 0.0) || sides.clone().all(|side| side <= 0.0);
+
+    on_segment(vertices[0], vertices[1]) || on_segment(vertices[1], vertices[2]) || in_bevel
+}
+
+#[test]
+fn stroke_with_short_lines_matches_supersampling() {
+    let mut rng = rand::thread_rng();
+    let samples = 16;
+
+    for _ in 0..NUM_TESTS / 100 {
+        let vertices = [
+            random_point(&mut rng, 5.0),
+            random_point(&mut rng, 5.0),
+            random_point(&mut rng, 5.0),
+        ];
+        let width = rng.gen_range(0.5..6.0);
+
+        // Lines that double back over themselves aren't covered exactly
+        let unit = |a: Point<f64>, b: Point<f64>| {
+            let length = (b.0 - a.0).hypot(b.1 - a.1);
+            ((b.0 - a.0) / length, (b.1 - a.1) / length)
+        };
+        let (before, after) = (
+            unit(vertices[0], vertices[1]),
+            unit(vertices[1], vertices[2]),
+        );
+        if before.0 * after.0 + before.1 * after.1 <= -0.9 {
+            continue;
+        }
+
+        let mut buffer = [0.0; 64];
+        let outline = StrokeOutline::new(&vertices, width, StrokeCap::Butt, StrokeJoin::Bevel, 0.1);
+        let pixels = outline
+            .fill_antialiased::<isize>(&mut buffer)
+            .collect::<HashMap<_, _>>();
+
+        // Every pixel the stroke could reach, so that missing ones are checked too
+        let reach = width.ceil() as isize;
+        let (low, high) = (-5 - reach, 5 + reach);
+
+        for x in low..=high {
+            for y in low..=high {
+                let mut covered = 0;
+
+                for i in 0..samples {
+                    for j in 0..samples {
+                        let offset = |index| (index as f64 + 0.5) / samples as f64 - 0.5;
+                        let point = (x as f64 + offset(i), y as f64 + offset(j));
+                        covered += stroke_contains(&vertices, width, point) as usize;
+                    }
+                }
+
+                let value = pixels.get(&(x, y)).cloned().unwrap_or(0.0);
+                let expected = covered as f64 / (samples * samples) as f64;
+                assert!(
+                    (value - expected).abs() < 0.1,
+                    "{:?} {} {:?}",
+                    vertices,
+                    width,
+                    (x, y)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn bresenham_ellipse_matches_circle() {
+    for radius in 1..200 {
+        let mut circle = BresenhamCircle::new(0, 0, radius).collect::<Vec<_>>();
+        let mut ellipse = BresenhamEllipse::new(0, 0, radius, radius).collect::<Vec<_>>();
+        circle.sort();
+        ellipse.sort();
+        assert_eq!(circle, ellipse);
+    }
+}
+
+#[test]
+fn bresenham_ellipse_has_no_gaps() {
+    use std::collections::HashSet;
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let rect = random_rect(&mut rng, RANGE);
+
+        let points = BresenhamEllipse::from_rect(rect).collect::<Vec<_>>();
+        let set = points.iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(set.len(), points.len());
+
+        // It's symmetrical and touches every side of the rectangle
+        for &(x, y) in &points {
+            assert!(inside(&(x, y), &rect));
+            assert!(set.contains(&((rect.0).0 + (rect.1).0 - x, y)));
+            assert!(set.contains(&(x, (rect.0).1 + (rect.1).1 - y)));
+        }
+
+        for &(x, y) in &[rect.0, rect.1] {
+            assert!(points.iter().any(|point| point.0 == x));
+            assert!(points.iter().any(|point| point.1 == y));
+        }
+
+        // Every point can be reached from the first one by moving to a neighbour
+        let mut reached = HashSet::new();
+        let mut stack = vec![points[0]];
+        reached.insert(points[0]);
+
+        while let Some((x, y)) = stack.pop() {
+            for dx in -1..2 {
+                for dy in -1..2 {
+                    let next = (x + dx, y + dy);
+                    if set.contains(&next) && reached.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(reached.len(), points.len());
+    }
+}
+
+#[test]
+fn rotated_ellipse_has_no_gaps() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        let center = random_point(&mut rng, RANGE_FLOAT as f64);
+        let radius_x = rng.gen_range(0.5..RANGE_FLOAT as f64);
+        let angle = rng.gen_range(-10.0..10.0);
+
+        // Thin ellipses turn back on themselves at their tips
+        let radius_y = if rng.gen() {
+            rng.gen_range(0.5..RANGE_FLOAT as f64)
+        } else {
+            rng.gen_range(0.5..2.0)
+        };
+
+        let points =
+            RotatedEllipse::<f64, i32>::new(center, radius_x, radius_y, angle).collect::<Vec<_>>();
+
+        // Each point is next to the one before it, all the way around the loop, and is never
+        // the same as it
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+            assert!(a != b || points.len() == 1);
+        }
+
+        // And none of them are too far from the ellipse
+        let (sin, cos) = angle.sin_cos();
+        let distance = |x: i32, y: i32, t: f64| {
+            let (x, y) = (x as f64 - center.0, y as f64 - center.1);
+            let along = x * cos + y * sin - radius_x * t.cos();
+            let across = y * cos - x * sin - radius_y * t.sin();
+            along.hypot(across)
+        };
+
+        for &(x, y) in &points {
+            let steps = 256;
+            let step = std::f64::consts::PI * 2.0 / steps as f64;
+            let best = (0..steps)
+                .map(|i| i as f64 * step)
+                .min_by(|&a, &b| distance(x, y, a).partial_cmp(&distance(x, y, b)).unwrap())
+                .unwrap();
+
+            // Narrow it down around the nearest of the samples
+            let (mut low, mut high) = (best - step, best + step);
+            for _ in 0..40 {
+                let (a, b) = ((low * 2.0 + high) / 3.0, (low + high * 2.0) / 3.0);
+                if distance(x, y, a) < distance(x, y, b) {
+                    high = b;
+                } else {
+                    low = a;
+                }
+            }
+
+            assert!(distance(x, y, low) <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn arc_matches_circle() {
+    use std::f64::consts::PI;
+
+    let mut rng = rand::thread_rng();
+    let turn = 2.0 * PI;
+
+    for _ in 0..NUM_TESTS / 10 {
+        let center = random_point(&mut rng, RANGE);
+        let radius = rng.gen_range(0..RANGE / 5);
+        let start = rng.gen_range(-10.0..10.0);
+        let end = rng.gen_range(-10.0..10.0);
+
+        let arc = Arc::new(center.0, center.1, radius, start, end).collect::<Vec<_>>();
+
+        // It's the points of the circle within the arc, in order of how far they are from the start
+        let sweep: f64 = (end - start).abs().min(turn);
+        let sign = if end < start { -1.0 } else { 1.0 };
+
+        let mut points = BresenhamCircle::new(center.0, center.1, radius)
+            .filter_map(|(x, y)| {
+                let angle = ((y - center.1) as f64).atan2((x - center.0) as f64);
+                let along = ((angle - start) * sign).rem_euclid(turn);
+                let along = if turn - along < 1e-9 { 0.0 } else { along };
+
+                if along <= sweep + 1e-9 {
+                    Some((along, (x, y)))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert!(arc.iter().eq(points.iter().map(|point| &point.1)));
+
+        for pair in arc.windows(2) {
+            assert!((pair[0].0 - pair[1].0).abs() <= 1 && (pair[0].1 - pair[1].1).abs() <= 1);
+        }
+    }
+}
+
+#[test]
+fn whole_circle_is_one_loop() {
+    use std::collections::HashSet;
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 10 {
+        let center = random_point(&mut rng, RANGE);
+        let radius = rng.gen_range(1..RANGE / 5);
+        let start = rng.gen_range(-10.0..10.0);
+        let direction = if rng.gen() {
+            ArcDirection::Clockwise
+        } else {
+            ArcDirection::Counterclockwise
+        };
+
+        let points =
+            Arc::whole_circle(center.0, center.1, radius, start, direction).collect::<Vec<_>>();
+        let set = points.iter().cloned().collect::<HashSet<_>>();
+        let circle = BresenhamCircle::new(center.0, center.1, radius).collect::<Vec<_>>();
+
+        // The circle doesn't return any points twice either
+        assert_eq!(set.len(), points.len());
+        assert_eq!(circle.len(), points.len());
+        assert!(circle.iter().all(|point| set.contains(point)));
+
+        // Each point is next to the one before it, all the way around the loop, and is never
+        // the same as it
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            assert!((a.0 - b.0).abs() <= 1 && (a.1 - b.1).abs() <= 1);
+            assert!(a != b || points.len() == 1);
+        }
+    }
+}
+
+#[test]
+fn annulus_matches_circles() {
+    use std::collections::HashSet;
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NUM_TESTS / 100 {
+        let center = random_point(&mut rng, RANGE);
+        let outer = rng.gen_range(0..RANGE / 5);
+        let inner = rng.gen_range(0..outer + 1);
+
+        let mut points = HashSet::new();
+        let mut last = None;
+
+        for ((x, y), length) in Annulus::new(center.0, center.1, inner, outer) {
+            // Spans go from left to right and top to bottom, and don't overlap
+            assert!(last < Some((y, x)));
+            last = Some((y, x + length as isize - 1));
+
+            for i in 0..length as isize {
+                points.insert((x + i, y));
+            }
+        }
+
+        // Each span ends on one of the circles
+        let outline = BresenhamCircle::new(center.0, center.1, outer)
+            .chain(BresenhamCircle::new(center.0, center.1, inner))
+            .collect::<HashSet<_>>();
+
+        assert!(outline.iter().all(|point| points.contains(point)));
+
+        for &(x, y) in &points {
+            if !points.contains(&(x - 1, y)) || !points.contains(&(x + 1, y)) {
+                assert!(outline.contains(&(x, y)));
+            }
+        }
+
+        // Without a hole, it's the same as a filled circle
+        assert!(Annulus::new(center.0, center.1, 0, outer)
+            .eq(FilledCircle::new(center.0, center.1, outer)));
+    }
+}
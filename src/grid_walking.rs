@@ -29,12 +29,15 @@ use steps::Steps;
 /// [article]: http://www.redblobgames.com/grids/line-drawing.html
 pub struct WalkGrid<T> {
     point: Point<T>,
-    ix: f32,
-    iy: f32,
+    back: Point<T>,
+    start: Point<T>,
+    error: T,
+    back_error: T,
+    remaining: usize,
     sign_x: T,
     sign_y: T,
-    ny: f32,
-    nx: f32,
+    ny: T,
+    nx: T,
 }
 
 impl<T: SignedNum> WalkGrid<T> {
@@ -42,15 +45,20 @@ impl<T: SignedNum> WalkGrid<T> {
     pub fn new(start: Point<T>, end: Point<T>) -> WalkGrid<T> {
         // Delta values between the points
         let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let (nx, ny) = (dx.abs(), dy.abs());
+        let (error, _) = initial_error(nx, ny);
 
         WalkGrid {
             point: start,
-            ix: 0.0,
-            iy: 0.0,
+            back: end,
+            start,
+            error,
+            back_error: error,
+            remaining: nx.to_usize().unwrap() + ny.to_usize().unwrap() + 1,
             sign_x: dx.signum(),
             sign_y: dy.signum(),
-            nx: dx.abs().to_f32().unwrap(),
-            ny: dy.abs().to_f32().unwrap(),
+            nx,
+            ny,
         }
     }
 
@@ -65,15 +73,19 @@ impl<T: SignedNum> Iterator for WalkGrid<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ix <= self.nx && self.iy <= self.ny {
+        if self.remaining > 0 {
+            self.remaining -= 1;
             let point = self.point;
 
-            if (0.5 + self.ix) / self.nx < (0.5 + self.iy) / self.ny {
-                self.point.0 += self.sign_x;
-                self.ix += 1.0;
-            } else {
-                self.point.1 += self.sign_y;
-                self.iy += 1.0;
+            // Don't step past the end, in case it's at the edge of the range of `T`
+            if self.remaining > 0 {
+                if self.error < T::zero() {
+                    self.point.0 += self.sign_x;
+                    self.error += self.ny;
+                } else {
+                    self.point.1 += self.sign_y;
+                    self.error -= self.nx;
+                }
             }
 
             Some(point)
@@ -81,8 +93,42 @@ impl<T: SignedNum> Iterator for WalkGrid<T> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
+impl<T: SignedNum> DoubleEndedIterator for WalkGrid<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            let point = self.back;
+
+            // Undo whichever step the forward walk would have taken last
+            if self.remaining > 0 {
+                if self.back.1 == self.start.1
+                    || (self.back.0 != self.start.0 && self.back_error >= self.ny - self.nx)
+                {
+                    self.back.0 -= self.sign_x;
+                    self.back_error -= self.ny;
+                } else {
+                    self.back.1 -= self.sign_y;
+                    self.back_error += self.nx;
+                }
+            }
+
+            Some(point)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: SignedNum> ExactSizeIterator for WalkGrid<T> {}
+
 /// Like [`WalkGrid`] but takes diagonal steps if the line passes directly over a corner.
 ///
 /// See [this section][section] of the [article] for an interactive demonstration.
@@ -111,12 +157,16 @@ impl<T: SignedNum> Iterator for WalkGrid<T> {
 /// [article]: http://www.redblobgames.com/grids/line-drawing.html
 pub struct Supercover<T> {
     point: Point<T>,
-    ix: f32,
-    iy: f32,
+    back: Point<T>,
+    start: Point<T>,
+    error: T,
+    back_error: T,
+    even: bool,
+    remaining: usize,
     sign_x: T,
     sign_y: T,
-    ny: f32,
-    nx: f32,
+    ny: T,
+    nx: T,
 }
 
 impl<T: SignedNum> Supercover<T> {
@@ -124,15 +174,31 @@ impl<T: SignedNum> Supercover<T> {
     pub fn new(start: Point<T>, end: Point<T>) -> Self {
         // Delta values between the points
         let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let (nx, ny) = (dx.abs(), dy.abs());
+        let (error, even) = initial_error(nx, ny);
+
+        // The line passes directly over a corner once per repetition of its reduced slope, but
+        // only if both halves of that slope are odd
+        let (ux, uy) = (nx.to_usize().unwrap(), ny.to_usize().unwrap());
+        let repeats = gcd(ux, uy);
+        let corners = if ux > 0 && uy > 0 && (ux / repeats) % 2 == 1 && (uy / repeats) % 2 == 1 {
+            repeats
+        } else {
+            0
+        };
 
         Self {
             point: start,
-            ix: 0.0,
-            iy: 0.0,
+            back: end,
+            start,
+            error,
+            back_error: error,
+            even,
+            remaining: ux + uy + 1 - corners,
             sign_x: dx.signum(),
             sign_y: dy.signum(),
-            nx: dx.abs().to_f32().unwrap(),
-            ny: dy.abs().to_f32().unwrap(),
+            nx,
+            ny,
         }
     }
 
@@ -147,23 +213,66 @@ impl<T: SignedNum> Iterator for Supercover<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.ix <= self.nx && self.iy <= self.ny {
+        if self.remaining > 0 {
+            self.remaining -= 1;
             let point = self.point;
 
-            let comparison = ((0.5 + self.ix) / self.nx) - ((0.5 + self.iy) / self.ny);
-
-            // If the comparison is equal then jump diagonally
-            if comparison == 0.0 {
-                self.point.0 += self.sign_x;
-                self.point.1 += self.sign_y;
-                self.ix += 1.0;
-                self.iy += 1.0;
-            } else if comparison < 0.0 {
-                self.point.0 += self.sign_x;
-                self.ix += 1.0;
-            } else {
-                self.point.1 += self.sign_y;
-                self.iy += 1.0;
+            // Don't step past the end, in case it's at the edge of the range of `T`
+            if self.remaining > 0 {
+                // If the comparison is equal then jump diagonally
+                if self.even && self.error.is_zero() {
+                    self.point.0 += self.sign_x;
+                    self.point.1 += self.sign_y;
+                    self.error += self.ny - self.nx;
+                } else if self.error < T::zero() {
+                    self.point.0 += self.sign_x;
+                    self.error += self.ny;
+                } else {
+                    self.point.1 += self.sign_y;
+                    self.error -= self.nx;
+                }
+            }
+
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for Supercover<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            let point = self.back;
+
+            // Undo whichever step the forward walk would have taken last
+            if self.remaining > 0 {
+                let threshold = self.ny - self.nx;
+
+                if self.back.0 == self.start.0 {
+                    self.back.1 -= self.sign_y;
+                    self.back_error += self.nx;
+                } else if self.back.1 == self.start.1 {
+                    self.back.0 -= self.sign_x;
+                    self.back_error -= self.ny;
+                } else if self.even && self.back_error == threshold {
+                    self.back.0 -= self.sign_x;
+                    self.back.1 -= self.sign_y;
+                    self.back_error -= threshold;
+                } else if self.back_error >= threshold {
+                    self.back.0 -= self.sign_x;
+                    self.back_error -= self.ny;
+                } else {
+                    self.back.1 -= self.sign_y;
+                    self.back_error += self.nx;
+                }
             }
 
             Some(point)
@@ -173,6 +282,35 @@ impl<T: SignedNum> Iterator for Supercover<T> {
     }
 }
 
+impl<T: SignedNum> ExactSizeIterator for Supercover<T> {}
+
+/// Get the starting error term for walking a grid `nx` by `ny` cells, and whether it's even.
+///
+/// The walk compares `(0.5 + ix) / nx` with `(0.5 + iy) / ny`. Cross-multiplying that gives
+/// `(1 + 2 * ix) * ny - (1 + 2 * iy) * nx`, which is tracked halved and rounded down so that it
+/// stays within `-nx..=ny` and can't overflow.
+#[inline]
+fn initial_error<T: SignedNum>(nx: T, ny: T) -> (T, bool) {
+    let difference = ny - nx;
+    let mut parity = difference % T::cast(2);
+    if parity < T::zero() {
+        parity += T::cast(2);
+    }
+
+    ((difference - parity) / T::cast(2), parity.is_zero())
+}
+
+#[inline]
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+
+    a
+}
+
 #[test]
 fn walk_grid_tests() {
     use fuzzing::reverse_slice;
@@ -238,3 +376,52 @@ fn supercover_tests() {
     // otherwise it should do the same as walk grid
     assert_eq!(supercover((0, 0), (4, 5)), walk_grid((0, 0), (4, 5)));
 }
+
+#[test]
+fn exact_tests() {
+    // These would lose precision as floats
+    let big = 1i64 << 40;
+
+    assert_eq!(
+        Supercover::new((0, 0), (3 * big, big))
+            .take(7)
+            .collect::<Vec<_>>(),
+        Supercover::new((0, 0), (30, 10))
+            .take(7)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Supercover::new((0, 0), (3 * big, big)).len() as i64,
+        3 * big + 1
+    );
+
+    assert_eq!(
+        WalkGrid::new((0, 0), (-big, 2 * big + 1))
+            .rev()
+            .take(5)
+            .collect::<Vec<_>>(),
+        [
+            (-big, 2 * big + 1),
+            (-big, 2 * big),
+            (-big + 1, 2 * big),
+            (-big + 1, 2 * big - 1),
+            (-big + 1, 2 * big - 2)
+        ]
+    );
+
+    // Lines that end at the edge of the range shouldn't overflow
+    assert_eq!(
+        WalkGrid::new((125i8, -126), (127, -128)).collect::<Vec<_>>(),
+        [
+            (125, -126),
+            (125, -127),
+            (126, -127),
+            (126, -128),
+            (127, -128)
+        ]
+    );
+    assert_eq!(
+        Supercover::new((127i8, -128), (125, -126)).collect::<Vec<_>>(),
+        [(127, -128), (126, -127), (125, -126)]
+    );
+}
@@ -0,0 +1,156 @@
+use {FloatNum, Point, SignedNum};
+use octant::Octant;
+use steps::Steps;
+
+/// An implementation of [Gupta and Sproull's anti-aliased line algorithm].
+///
+/// Like [`XiaolinWu`], this returns an extra variable for how much a point is covered, but it's
+/// based on the perpendicular distance from the centre of each point to the line instead of the
+/// vertical or horizontal distance. The line is one pixel wide, and is blurred with a cone filter
+/// with a radius of one pixel, so lines look equally bright at every angle. Each step along the
+/// line returns three points across it, and the points are returned in order from the start of
+/// the line to the end.
+///
+/// The three points catch almost all of the blur, so the total brightness of a line stays within a
+/// few percent of its length, where [`XiaolinWu`] lines get about 30% dimmer at 45 degrees.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::GuptaSproull;
+///
+/// fn main() {
+///     for ((x, y), value) in GuptaSproull::<f32, i8>::new((0.0, 0.0), (2.0, 1.0)) {
+///         print!("(({}, {}), {:.2}), ", x, y, value);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((0, -1), 0.17), ((0, 0), 0.78), ((0, 1), 0.17), ((1, 0), 0.55), ((1, 1), 0.55), ((1, 2), 0.01), ((2, 0), 0.17), ((2, 1), 0.78), ((2, 2), 0.17),
+/// ```
+///
+/// [Gupta and Sproull's anti-aliased line algorithm]: https://en.wikipedia.org/wiki/Spatial_anti-aliasing
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+pub struct GuptaSproull<I, O> {
+    octant: Octant,
+    gradient: I,
+    scale: I,
+    x: O,
+    y: I,
+    end_x: O,
+    offset: O,
+}
+
+impl<I: FloatNum, O: SignedNum> GuptaSproull<I, O> {
+    #[inline]
+    pub fn new(start: Point<I>, end: Point<I>) -> Self {
+        let octant = Octant::new(start, end);
+        let start = octant.to(start);
+        let end = octant.to(end);
+
+        let dx = end.0 - start.0;
+        let dy = end.1 - start.1;
+
+        let (gradient, scale) = if dx.is_zero() {
+            (I::zero(), I::one())
+        } else {
+            (dy / dx, dx / dx.hypot(dy))
+        };
+
+        let x = start.0.round();
+
+        Self {
+            octant,
+            gradient,
+            scale,
+            x: O::cast(x),
+            y: start.1 + (x - start.0) * gradient,
+            end_x: O::cast(end.0.round()),
+            offset: -O::one(),
+        }
+    }
+
+    #[inline]
+    pub fn steps(self) -> Steps<(Point<O>, I), Self> {
+        Steps::new(self)
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for GuptaSproull<I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x <= self.end_x {
+            let centre = self.y.round();
+            let y = centre + I::cast(self.offset);
+
+            let distance = (y - self.y).abs() * self.scale;
+            let point = self.octant.from((self.x, O::cast(y)));
+
+            // Move across the line, and then onto the next step along it
+            if self.offset < O::one() {
+                self.offset += O::one();
+            } else {
+                self.offset = -O::one();
+                self.x += O::one();
+                self.y += self.gradient;
+            }
+
+            Some((point, intensity(distance)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Get the brightness of a point `distance` away from the centre of a line one pixel wide, after
+/// being blurred by a cone filter with a radius and volume of one.
+#[inline]
+fn intensity<I: FloatNum>(distance: I) -> I {
+    let half = I::cast(0.5);
+    cone_beyond(distance - half) - cone_beyond(distance + half)
+}
+
+/// Get the volume of the cone filter past a straight cut `distance` from its centre.
+#[inline]
+fn cone_beyond<I: FloatNum>(distance: I) -> I {
+    let d = distance.abs();
+
+    let volume = if d >= I::one() {
+        I::zero()
+    } else {
+        let s = (I::one() - d * d).sqrt();
+        let sech = if d.is_zero() {
+            I::zero()
+        } else {
+            d * d * d * ((I::one() + s) / d).ln()
+        };
+
+        I::cast(0.5) - (I::cast(2) * d * s + d.asin() - sech) / I::cast(core::f64::consts::PI)
+    };
+
+    if distance < I::zero() {
+        I::one() - volume
+    } else {
+        volume
+    }
+}
+
+#[test]
+fn tests() {
+    let gupta_sproull = |a, b| GuptaSproull::<f64, i32>::new(a, b).collect::<Vec<_>>();
+
+    let line = gupta_sproull((0.0, 0.0), (3.0, 0.0));
+    assert_eq!(line.len(), 12);
+    assert!((line[1].1 - 0.780).abs() < 0.001);
+    assert!((line[0].1 - 0.110).abs() < 0.001);
+    assert!((line[0].1 + line[1].1 + line[2].1 - 1.0).abs() < 1e-9);
+
+    // Lines are returned from start to end
+    let line = gupta_sproull((4.0, 5.0), (2.0, 1.0));
+    assert_eq!(line[1].0, (4, 5));
+    assert_eq!(line[line.len() - 2].0, (2, 1));
+}
@@ -4,16 +4,49 @@
 //!
 //! * [`Bresenham`] - An implementation of [Bresenham's line algorithm].
 //! * [`Bresenham3d`] - A 3-Dimensional implementation of bresenham.
+//! * [`SymmetricBresenham`] and [`SymmetricBresenham3d`] - Versions of bresenham that give the
+//!   same points when the start and end are swapped.
 //! * [`BresenhamCircle`] - Bresenham's circle algorithm.
+//! * [`BresenhamEllipse`] - Bresenham's algorithm for axis-aligned ellipses.
+//! * [`RotatedEllipse`] - Ellipses turned to any angle, made of rational Bézier curves.
+//! * [`Arc`] - Parts of circles between two angles, or whole circles as one loop, walked in order
+//!   in either direction.
+//! * [`FilledCircle`] and [`Annulus`] - Filled circles and rings as horizontal spans, which end
+//!   on the points of [`BresenhamCircle`].
+//! * [`XiaolinWuCircle`] - Anti-aliased circles in the style of Xiaolin Wu's algorithm.
 //! * [`Midpoint`] - The [mid-point line algorithm].
 //! * [`WalkGrid`] and [`Supercover`] - implemented from [this article by Red Blob Games][article].
 //! * [`WalkVoxels`] - A similar 3-Dimensional algorithm that only takes orthogonal steps.
 //! * [`XiaolinWu`] - [Xiaolin Wu's line algorithm].
+//! * [`GuptaSproull`] - [Gupta and Sproull's anti-aliased line algorithm], for evenly bright lines.
+//! * [`ThickLine`] - Lines with a configurable width, in the spirit of [Murphy's algorithm].
+//! * [`AntialiasedThickLine`] - Anti-aliased lines with a configurable width, using exact area
+//!   coverage.
+//! * [`Transfer`], [`AlphaTable`] and [`AntiAliased`] - Gamma and sRGB correct values for drawing
+//!   anti-aliased coverage.
+//! * [`Polyline`] and [`PolygonOutline`] - Connected lines that only return each joint once.
+//! * [`PolygonFill`] - Scanline polygon filling with even-odd and non-zero winding rules.
+//! * [`AntialiasedPolygonFill`] - Anti-aliased polygon filling with a signed area accumulation
+//!   buffer.
+//! * [`Path`] - Paths made of lines and Bézier curves, which can be stroked or filled, and parsed
+//!   from SVG path data.
+//! * [`StrokeOutline`] - Outlines of wide polylines with round, square or butt caps and miter,
+//!   round or bevel joins, for filling.
+//! * [`DistanceField`] - Exact and signed distances from each cell of a grid to segments and
+//!   circles.
 //!
 //! [`Bresenham`]: struct.Bresenham.html
 //! [Bresenham's line algorithm]: https://en.wikipedia.org/wiki/Bresenham's_line_algorithm
 //! [`Bresenham3d`]: struct.Bresenham3d.html
+//! [`SymmetricBresenham`]: struct.SymmetricBresenham.html
+//! [`SymmetricBresenham3d`]: struct.SymmetricBresenham3d.html
 //! [`BresenhamCircle`]: struct.BresenhamCircle.html
+//! [`BresenhamEllipse`]: struct.BresenhamEllipse.html
+//! [`RotatedEllipse`]: struct.RotatedEllipse.html
+//! [`Arc`]: struct.Arc.html
+//! [`FilledCircle`]: struct.FilledCircle.html
+//! [`Annulus`]: struct.Annulus.html
+//! [`XiaolinWuCircle`]: struct.XiaolinWuCircle.html
 //! [`Midpoint`]: struct.Midpoint.html
 //! [mid-point line algorithm]: http://www.mat.univie.ac.at/~kriegl/Skripten/CG/node25.html
 //! [`WalkGrid`]: struct.WalkGrid.html
@@ -22,6 +55,21 @@
 //! [`XiaolinWu`]: struct.XiaolinWu.html
 //! [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
 //! [`WalkVoxels`]: struct.WalkVoxels.html
+//! [`GuptaSproull`]: struct.GuptaSproull.html
+//! [Gupta and Sproull's anti-aliased line algorithm]: https://en.wikipedia.org/wiki/Spatial_anti-aliasing
+//! [`ThickLine`]: struct.ThickLine.html
+//! [Murphy's algorithm]: http://kt8216.unixcab.org/murphy/index.html
+//! [`AntialiasedThickLine`]: struct.AntialiasedThickLine.html
+//! [`Transfer`]: enum.Transfer.html
+//! [`AlphaTable`]: struct.AlphaTable.html
+//! [`AntiAliased`]: trait.AntiAliased.html
+//! [`Polyline`]: struct.Polyline.html
+//! [`PolygonOutline`]: struct.PolygonOutline.html
+//! [`PolygonFill`]: struct.PolygonFill.html
+//! [`AntialiasedPolygonFill`]: struct.AntialiasedPolygonFill.html
+//! [`Path`]: struct.Path.html
+//! [`StrokeOutline`]: struct.StrokeOutline.html
+//! [`DistanceField`]: struct.DistanceField.html
 
 #![cfg_attr(not(test), no_std)]
 
@@ -31,6 +79,7 @@ use std as core;
 extern crate num_traits;
 
 pub mod steps;
+pub mod spans;
 pub mod octant;
 
 mod bresenham;
@@ -41,6 +90,23 @@ mod fuzzing;
 mod bresenham_3d;
 mod walk_voxels;
 mod bresenham_circle;
+mod bresenham_ellipse;
+mod arc;
+mod filled_circle;
+mod rotated_ellipse;
+mod xiaolin_wu_circle;
+mod thick_line;
+mod dashes;
+mod polyline;
+mod polygon_fill;
+mod antialiased_polygon_fill;
+mod path;
+mod svg_path;
+mod stroke;
+mod distance_field;
+mod gupta_sproull;
+mod antialiased_thick_line;
+mod coverage;
 
 pub use bresenham::*;
 pub use midpoint::*;
@@ -49,6 +115,23 @@ pub use grid_walking::*;
 pub use bresenham_3d::*;
 pub use walk_voxels::*;
 pub use bresenham_circle::*;
+pub use bresenham_ellipse::*;
+pub use arc::*;
+pub use filled_circle::*;
+pub use rotated_ellipse::*;
+pub use xiaolin_wu_circle::*;
+pub use thick_line::*;
+pub use dashes::*;
+pub use polyline::*;
+pub use polygon_fill::*;
+pub use antialiased_polygon_fill::*;
+pub use path::*;
+pub use svg_path::*;
+pub use stroke::*;
+pub use distance_field::*;
+pub use gupta_sproull::*;
+pub use antialiased_thick_line::*;
+pub use coverage::*;
 
 use num_traits::{Float, NumAssignOps, NumCast, Signed};
 
@@ -56,6 +139,8 @@ use num_traits::{Float, NumAssignOps, NumCast, Signed};
 pub type Point<T> = (T, T);
 /// An point in 3D space.
 pub type Voxel<T> = (T, T, T);
+/// An axis-aligned rectangle, given by its inclusive minimum and maximum corners.
+pub type Rect<T> = (Point<T>, Point<T>);
 
 /// All the floating-point primitives.
 pub trait FloatNum: Float + NumAssignOps {
@@ -76,3 +161,46 @@ pub trait SignedNum: Signed + Ord + Copy + NumCast + NumAssignOps {
 }
 
 impl<T: Signed + Ord + Copy + NumCast + NumAssignOps> SignedNum for T {}
+
+/// Divide, rounding towards negative infinity.
+#[inline]
+fn div_floor<T: SignedNum>(a: T, b: T) -> T {
+    let quotient = a / b;
+
+    if (a % b != T::zero()) && ((a < T::zero()) != (b < T::zero())) {
+        quotient - T::one()
+    } else {
+        quotient
+    }
+}
+
+/// Divide, rounding towards positive infinity.
+#[inline]
+fn div_ceil<T: SignedNum>(a: T, b: T) -> T {
+    -div_floor(-a, b)
+}
+
+/// The square root of a non-negative number, rounded down.
+#[inline]
+fn isqrt<T: SignedNum>(value: T) -> T {
+    if value <= T::one() {
+        return value;
+    }
+
+    let two = T::cast(2);
+    let mut root = value / two;
+    let mut next = (root + value / root) / two;
+
+    while next < root {
+        root = next;
+        next = (root + value / root) / two;
+    }
+
+    root
+}
+
+/// Widen a number to `i128`, so that products of coordinates can't overflow.
+#[inline]
+fn wide<T: SignedNum>(value: T) -> i128 {
+    value.to_i128().unwrap()
+}
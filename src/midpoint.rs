@@ -1,5 +1,8 @@
-use {FloatNum, Point, SignedNum};
+use {isqrt, FloatNum, Point, Rect, SignedNum};
+use core::cmp::{max, min};
+use num_traits::NumCast;
 use octant::Octant;
+use spans::Spans;
 use steps::Steps;
 
 /// An implementation of the [mid-point line drawing algorithm].
@@ -23,6 +26,16 @@ use steps::Steps;
 /// (0, 0), (1, 1), (1, 2), (1, 3), (2, 4), (2, 5), (2, 6), (3, 7), (3, 8),
 /// ```
 ///
+/// Unlike [`Bresenham`], the decision variable is a floating-point sum that's added up one step at
+/// a time, and it can't be worked out directly for a point along the line without rounding
+/// differently. So [`point_at`], [`nth`] and [`skip`] walk along the line to a point without
+/// returning the ones before it, which takes time proportional to how far they go. Iterating from
+/// the back walks forwards to each point from a little way behind it.
+///
+/// [`point_at`]: #method.point_at
+/// [`nth`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.nth
+/// [`skip`]: https://doc.rust-lang.org/core/iter/trait.Iterator.html#method.skip
+///
 /// [mid-point line drawing algorithm]: http://www.mat.univie.ac.at/~kriegl/Skripten/CG/node25.html
 /// [`Bresenham`]: struct.bresenham.html
 pub struct Midpoint<I, O> {
@@ -32,6 +45,11 @@ pub struct Midpoint<I, O> {
     b: I,
     k: I,
     end_x: O,
+    start: Point<O>,
+    start_k: I,
+    back: Point<O>,
+    back_k: I,
+    delta_x: O,
 }
 
 impl<I: FloatNum, O: SignedNum> Midpoint<I, O> {
@@ -50,20 +68,160 @@ impl<I: FloatNum, O: SignedNum> Midpoint<I, O> {
         let b = end.0 - start.0;
         let c = start.0 * end.1 - end.0 * start.1;
 
+        let point = (O::cast(start.0.round()), O::cast(start.1.round()));
+        let k = a * (start.0.round() + I::one()) + b * (start.1.round() + I::cast(0.5)) + c;
+        let end_x = O::cast(end.0.round());
+
         Self {
             octant,
             a,
             b,
-            point: (O::cast(start.0.round()), O::cast(start.1.round())),
-            k: a * (start.0.round() + I::one()) + b * (start.1.round() + I::cast(0.5)) + c,
-            end_x: O::cast(end.0.round()),
+            point,
+            k,
+            end_x,
+            start: point,
+            start_k: k,
+            back: point,
+            back_k: k,
+            delta_x: end_x - point.0,
         }
     }
 
+    /// Create a line that only contains the points that lie within `clip`.
+    ///
+    /// The points are exactly the ones that [`Midpoint::new`] would return inside of `clip`, so a
+    /// line that is split across several clip rectangles still joins up. The decision variable is
+    /// added up along the line to where it enters the clip in the same way as [`Midpoint::new`],
+    /// but without returning the points along the way.
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Midpoint;
+    ///
+    /// fn main() {
+    ///     let clipped = Midpoint::<f32, i8>::clipped((-10.0, -5.0), (10.0, 5.0), ((0, 0), (3, 3)));
+    ///     assert_eq!(clipped.collect::<Vec<_>>(), [(0, 0), (1, 1), (2, 1), (3, 2)]);
+    /// }
+    /// ```
+    ///
+    /// [`Midpoint::new`]: struct.Midpoint.html#method.new
+    #[inline]
+    pub fn clipped(start: Point<I>, end: Point<I>, clip: Rect<O>) -> Self {
+        let mut line = Self::new(start, end);
+        let (clip_min, clip_max) = line.octant.to_rect(clip);
+        let first_x = max(line.point.0, clip_min.0);
+        let last_x = min(line.end_x, clip_max.0);
+
+        if first_x <= last_x {
+            // The y values only ever increase, so walk up to where they enter the clip
+            let (mut point, mut k) = line.walk_to(line.point, line.k, first_x);
+
+            while point.0 <= last_x && point.1 < clip_min.1 {
+                let (next, next_k) = line.step(point, k);
+                point = next;
+                k = next_k;
+            }
+
+            if point.0 <= last_x && point.1 <= clip_max.1 {
+                line.point = point;
+                line.k = k;
+
+                // Carry on to where they leave it
+                while point.0 < last_x {
+                    let (next, next_k) = line.step(point, k);
+
+                    if next.1 > clip_max.1 {
+                        break;
+                    }
+
+                    point = next;
+                    k = next_k;
+                }
+
+                line.back = point;
+                line.back_k = k;
+                line.end_x = point.0;
+
+                return line;
+            }
+        }
+
+        // Nothing is inside the clip, so end the line before it starts
+        line.end_x = line.start.0 - O::one();
+        line
+    }
+
+    /// Get the point `index` steps along from the start of the line.
+    ///
+    /// This walks along the line from the start, or from the front if it has already been
+    /// iterated past the start, so it takes time proportional to how far it has to go. Returns
+    /// `None` if `index` is past either end of the line.
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::Midpoint;
+    ///
+    /// fn main() {
+    ///     let line = Midpoint::<f32, i8>::new((0.0, 0.0), (6.0, 3.0));
+    ///     assert_eq!(line.point_at(3), Some((3, 2)));
+    ///     assert_eq!(line.point_at(7), None);
+    /// }
+    /// ```
+    #[inline]
+    pub fn point_at(&self, index: O) -> Option<Point<O>> {
+        if index >= O::zero() && index <= self.delta_x {
+            let x = self.start.0 + index;
+
+            let (point, _) = if self.point.0 <= x {
+                self.walk_to(self.point, self.k, x)
+            } else {
+                self.walk_to(self.start, self.start_k, x)
+            };
+
+            Some(self.octant.from(point))
+        } else {
+            None
+        }
+    }
+
+    /// Take a step along the line from `point`, with the decision variable `k` there.
+    #[inline]
+    fn step(&self, mut point: Point<O>, mut k: I) -> (Point<O>, I) {
+        // Take an N step
+        if k <= I::zero() {
+            k += self.b;
+            point.1 += O::one();
+        }
+
+        // Take an E step
+        k += self.a;
+        point.0 += O::one();
+
+        (point, k)
+    }
+
+    /// Take steps along the line from `point` until reaching `x`.
+    #[inline]
+    fn walk_to(&self, mut point: Point<O>, mut k: I, x: O) -> (Point<O>, I) {
+        while point.0 < x {
+            let (next, next_k) = self.step(point, k);
+            point = next;
+            k = next_k;
+        }
+
+        (point, k)
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<Point<O>, Self> {
         Steps::new(self)
     }
+
+    #[inline]
+    pub fn spans(self) -> Spans<O, Self> {
+        let axis = self.octant.major_axis();
+        Spans::new(self, axis)
+    }
 }
 
 impl<I: FloatNum, O: SignedNum> Iterator for Midpoint<I, O> {
@@ -73,24 +231,89 @@ impl<I: FloatNum, O: SignedNum> Iterator for Midpoint<I, O> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.point.0 <= self.end_x {
             let point = self.octant.from(self.point);
+            let (next, k) = self.step(self.point, self.k);
+            self.point = next;
+            self.k = k;
+
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end_x - self.point.0 + O::one())
+            .max(O::zero())
+            .to_usize()
+            .unwrap();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match <O as NumCast>::from(n) {
+            Some(n) if n <= self.end_x - self.point.0 => {
+                let (point, k) = self.walk_to(self.point, self.k, self.point.0 + n);
+                self.point = point;
+                self.k = k;
+                self.next()
+            }
+            _ => {
+                self.end_x = self.point.0 - O::one();
+                None
+            }
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> DoubleEndedIterator for Midpoint<I, O> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.point.0 <= self.end_x {
+            // The back is walked to from a point behind it. Once that's been passed or left too
+            // far behind, it's moved to about the square root of the remaining length before the
+            // end, so reversing the whole line doesn't take time proportional to its square.
+            let gap = isqrt(self.end_x - self.point.0);
+
+            if self.back.0 > self.end_x || self.end_x - self.back.0 > gap * O::cast(2) {
+                let x = self.end_x - gap;
+                let (back, back_k) = if self.point.0 <= self.back.0 && self.back.0 < x {
+                    self.walk_to(self.back, self.back_k, x)
+                } else {
+                    self.walk_to(self.point, self.k, x)
+                };
 
-            // Take an N step
-            if self.k <= I::zero() {
-                self.k += self.b;
-                self.point.1 += O::one();
+                self.back = back;
+                self.back_k = back_k;
             }
 
-            // Take an E step
-            self.k += self.a;
-            self.point.0 += O::one();
+            let (point, _) = self.walk_to(self.back, self.back_k, self.end_x);
+            self.end_x -= O::one();
 
-            Some(point)
+            Some(self.octant.from(point))
         } else {
             None
         }
     }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match <O as NumCast>::from(n) {
+            Some(n) if n <= self.end_x - self.point.0 => {
+                self.end_x -= n;
+                self.next_back()
+            }
+            _ => {
+                self.end_x = self.point.0 - O::one();
+                None
+            }
+        }
+    }
 }
 
+impl<I: FloatNum, O: SignedNum> ExactSizeIterator for Midpoint<I, O> {}
+
 #[test]
 fn tests() {
     let midpoint = |a, b| Midpoint::new(a, b).collect::<Vec<_>>();
@@ -104,4 +327,13 @@ fn tests() {
         midpoint((0.0, 0.0), (6.0, 3.0)),
         [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2), (5, 3), (6, 3)]
     );
+
+    let clipped = |a, b, clip| Midpoint::clipped(a, b, clip).collect::<Vec<_>>();
+
+    assert_eq!(
+        clipped((0.0, 0.0), (6.0, 3.0), ((2, 0), (4, 10))),
+        [(2, 1), (3, 2), (4, 2)]
+    );
+
+    assert_eq!(clipped((0.0, 0.0), (-5.0, -5.0), ((1, 1), (3, 3))), []);
 }
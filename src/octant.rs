@@ -1,6 +1,7 @@
 //! A simple octant struct for transforming line points.
 
-use Point;
+use {Point, Rect};
+use spans::Axis;
 use core::ops::{Neg, Sub};
 use num_traits::Zero;
 
@@ -71,4 +72,27 @@ impl Octant {
             _ => unreachable!(),
         }
     }
+
+    /// Get the axis that a line in this octant moves along at every step.
+    #[inline]
+    pub fn major_axis(&self) -> Axis {
+        match self.value {
+            1 | 2 | 5 | 6 => Axis::Y,
+            _ => Axis::X,
+        }
+    }
+
+    /// Convert a rectangle to its position in the octant.
+    ///
+    /// The returned rectangle is normalised so that its first corner is still the minimum.
+    #[inline]
+    pub fn to_rect<T: Neg<Output = T> + PartialOrd + Copy>(&self, rect: Rect<T>) -> Rect<T> {
+        let a = self.to(rect.0);
+        let b = self.to(rect.1);
+
+        let (min_x, max_x) = if a.0 < b.0 { (a.0, b.0) } else { (b.0, a.0) };
+        let (min_y, max_y) = if a.1 < b.1 { (a.1, b.1) } else { (b.1, a.1) };
+
+        ((min_x, min_y), (max_x, max_y))
+    }
 }
@@ -0,0 +1,590 @@
+use {
+    AntialiasedPolygonFill, Edges, FillRule, FloatNum, LineAlgorithm, Point, SignedNum, XiaolinWu,
+};
+use polyline::Segment;
+use core::mem::replace;
+
+/// A single step of a [`Path`].
+///
+/// [`Path`]: struct.Path.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand<I> {
+    /// Start a new subpath at a point.
+    MoveTo(Point<I>),
+    /// Draw a straight line to a point.
+    LineTo(Point<I>),
+    /// Draw a quadratic Bézier curve with a control point, to an end point.
+    QuadTo(Point<I>, Point<I>),
+    /// Draw a cubic Bézier curve with two control points, to an end point.
+    CubicTo(Point<I>, Point<I>, Point<I>),
+    /// Draw a rational quadratic Bézier curve with a control point, to an end point. The last
+    /// value is the weight of the control point, and weights below one give arcs of ellipses.
+    ConicTo(Point<I>, Point<I>, I),
+    /// Draw a straight line back to the start of the subpath.
+    Close,
+}
+
+/// A vector path made of lines and curves, built up a command at a time.
+///
+/// No memory is allocated, so the commands are stored in a buffer that has to be given up front.
+/// The path can then be drawn as an aliased stroke with [`stroke`], an anti-aliased stroke with
+/// [`stroke_antialiased`] or filled with [`fill`]. Each of these flattens the curves into
+/// straight lines that are no further than `tolerance` from the real curve, and draws those with
+/// the crate's other iterators.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{LineAlgorithm, Path, PathCommand};
+///
+/// fn main() {
+///     let mut buffer = [PathCommand::Close; 4];
+///     let mut path = Path::new(&mut buffer);
+///     path.move_to((0.0, 0.0)).quad_to((4.0, 0.0), (4.0, 4.0)).close();
+///
+///     for (x, y) in path.stroke::<i8>(0.25, &LineAlgorithm::Bresenham) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 0), (3, 1), (4, 2), (4, 3), (4, 4), (3, 3), (2, 2), (1, 1),
+/// ```
+///
+/// [`stroke`]: #method.stroke
+/// [`stroke_antialiased`]: #method.stroke_antialiased
+/// [`fill`]: #method.fill
+pub struct Path<'a, I: 'a> {
+    commands: &'a mut [PathCommand<I>],
+    length: usize,
+}
+
+impl<'a, I: FloatNum> Path<'a, I> {
+    /// Start an empty path that stores its commands in `buffer`.
+    #[inline]
+    pub fn new(buffer: &'a mut [PathCommand<I>]) -> Self {
+        Self {
+            commands: buffer,
+            length: 0,
+        }
+    }
+
+    /// Start a new subpath at `point`.
+    ///
+    /// # Panics
+    ///
+    /// This, and the other functions that add a command, panic if the buffer is full.
+    #[inline]
+    pub fn move_to(&mut self, point: Point<I>) -> &mut Self {
+        self.push(PathCommand::MoveTo(point))
+    }
+
+    /// Draw a straight line to `point`.
+    #[inline]
+    pub fn line_to(&mut self, point: Point<I>) -> &mut Self {
+        self.push(PathCommand::LineTo(point))
+    }
+
+    /// Draw a quadratic Bézier curve to `end`.
+    #[inline]
+    pub fn quad_to(&mut self, control: Point<I>, end: Point<I>) -> &mut Self {
+        self.push(PathCommand::QuadTo(control, end))
+    }
+
+    /// Draw a cubic Bézier curve to `end`.
+    #[inline]
+    pub fn cubic_to(&mut self, first: Point<I>, second: Point<I>, end: Point<I>) -> &mut Self {
+        self.push(PathCommand::CubicTo(first, second, end))
+    }
+
+    /// Draw a rational quadratic Bézier curve to `end`, where `weight` is how strongly the curve
+    /// is pulled towards `control`.
+    #[inline]
+    pub fn conic_to(&mut self, control: Point<I>, end: Point<I>, weight: I) -> &mut Self {
+        self.push(PathCommand::ConicTo(control, end, weight))
+    }
+
+    /// Draw a straight line back to the start of the current subpath.
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        self.push(PathCommand::Close)
+    }
+
+    /// Get the commands that have been added so far.
+    #[inline]
+    pub fn commands(&self) -> &[PathCommand<I>] {
+        &self.commands[..self.length]
+    }
+
+    /// Flatten the path into straight lines.
+    #[inline]
+    pub fn flatten(&self, tolerance: I) -> Flatten<'_, I> {
+        Flatten::new(self.commands(), tolerance)
+    }
+
+    /// Draw the outline of the path with an aliased line algorithm.
+    #[inline]
+    pub fn stroke<O: SignedNum>(
+        &self,
+        tolerance: I,
+        algorithm: &LineAlgorithm,
+    ) -> PathStroke<'_, I, O> {
+        PathStroke::new(self.commands(), tolerance, algorithm)
+    }
+
+    /// Draw the outline of the path with anti-aliased [`XiaolinWu`] lines.
+    ///
+    /// [`XiaolinWu`]: struct.XiaolinWu.html
+    #[inline]
+    pub fn stroke_antialiased<O: SignedNum>(
+        &self,
+        tolerance: I,
+    ) -> AntialiasedPathStroke<'_, I, O> {
+        AntialiasedPathStroke::new(self.commands(), tolerance)
+    }
+
+    /// Fill the path with an [`AntialiasedPolygonFill`], closing any subpaths that are still
+    /// open.
+    ///
+    /// [`AntialiasedPolygonFill`]: struct.AntialiasedPolygonFill.html
+    #[inline]
+    pub fn fill<'b, O: SignedNum>(
+        &'b self,
+        tolerance: I,
+        rule: FillRule,
+        buffer: &'b mut [I],
+    ) -> AntialiasedPolygonFill<'b, I, O, Flatten<'b, I>> {
+        AntialiasedPolygonFill::with_edges(self.flatten(tolerance), rule, buffer)
+    }
+
+    /// Check if there's no room left in the buffer for another command.
+    #[inline]
+    pub(crate) fn is_full(&self) -> bool {
+        self.length == self.commands.len()
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, command: PathCommand<I>) -> &mut Self {
+        assert!(self.length < self.commands.len(), "the path is full");
+
+        self.commands[self.length] = command;
+        self.length += 1;
+        self
+    }
+}
+
+/// A Bézier curve that is being flattened, which returns the end of each line in turn.
+#[derive(Clone, Copy)]
+pub(crate) struct Curve<I> {
+    points: [Point<I>; 4],
+    cubic: bool,
+    weight: I,
+    step: usize,
+    steps: usize,
+}
+
+impl<I: FloatNum> Curve<I> {
+    /// Work out how many straight lines are needed to stay within `tolerance` of the curve, using
+    /// the bound on the distance between a curve and its chord from its second derivative.
+    ///
+    /// Quadratic curves can have a `weight` on their control point, which makes the curve speed up
+    /// and slow down along its length. The bound is made larger to make up for that, which works
+    /// well for weights that aren't too far from one.
+    #[inline]
+    pub(crate) fn new(points: [Point<I>; 4], cubic: bool, weight: I, tolerance: I) -> Self {
+        let bend = |a: Point<I>, b: Point<I>, c: Point<I>| {
+            (a.0 - b.0 - b.0 + c.0).hypot(a.1 - b.1 - b.1 + c.1)
+        };
+
+        let scale = if cubic {
+            let bend =
+                bend(points[0], points[1], points[2]).max(bend(points[1], points[2], points[3]));
+            bend * I::cast(0.75)
+        } else {
+            bend(points[0], points[1], points[2]) * I::cast(0.25) * weight.max(weight.recip())
+        };
+
+        let steps = (scale / tolerance).sqrt().ceil().to_usize().unwrap_or(1);
+
+        Self {
+            points,
+            cubic,
+            weight,
+            step: 0,
+            steps: steps.max(1),
+        }
+    }
+
+    /// Use at least enough lines that none of them are longer than `length`.
+    ///
+    /// The lines around the control points are longer than the curve, and a weight can make the
+    /// curve move faster over part of its length, so this is made larger to make up for both.
+    #[inline]
+    pub(crate) fn max_length(self, length: I) -> Self {
+        let p = &self.points;
+        let count = if self.cubic { 3 } else { 2 };
+        let around = (0..count).fold(I::zero(), |total, i| {
+            total + (p[i + 1].0 - p[i].0).hypot(p[i + 1].1 - p[i].1)
+        });
+
+        let around = around * self.weight.max(self.weight.recip());
+        let steps = (around / length).ceil().to_usize().unwrap_or(1);
+
+        Self {
+            steps: self.steps.max(steps),
+            ..self
+        }
+    }
+
+    /// Get the point a fraction `t` of the way along the curve.
+    #[inline]
+    fn at(&self, t: I) -> Point<I> {
+        let s = I::one() - t;
+        let p = &self.points;
+
+        let weights = if self.cubic {
+            [
+                s * s * s,
+                I::cast(3) * s * s * t,
+                I::cast(3) * s * t * t,
+                t * t * t,
+            ]
+        } else {
+            [s * s, I::cast(2) * self.weight * s * t, t * t, I::zero()]
+        };
+
+        let total = weights
+            .iter()
+            .fold(I::zero(), |total, &weight| total + weight);
+
+        let point = weights
+            .iter()
+            .zip(p.iter())
+            .fold((I::zero(), I::zero()), |point, (&weight, &p)| {
+                (point.0 + p.0 * weight, point.1 + p.1 * weight)
+            });
+
+        (point.0 / total, point.1 / total)
+    }
+}
+
+impl<I: FloatNum> Iterator for Curve<I> {
+    type Item = Point<I>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == self.steps {
+            return None;
+        }
+
+        self.step += 1;
+
+        // Finish exactly on the end point
+        if self.step < self.steps {
+            Some(self.at(I::cast(self.step) / I::cast(self.steps)))
+        } else {
+            Some(self.points[if self.cubic { 3 } else { 2 }])
+        }
+    }
+}
+
+/// The straight lines that make up a [`Path`], with the curves flattened.
+///
+/// Lines are returned as `(start, end)` pairs, in order along the path. Lines with no length
+/// are skipped.
+///
+/// [`Path`]: struct.Path.html
+#[derive(Clone)]
+pub struct Flatten<'a, I: 'a> {
+    commands: &'a [PathCommand<I>],
+    tolerance: I,
+    index: usize,
+    current: Point<I>,
+    start: Point<I>,
+    curve: Option<Curve<I>>,
+    close_subpaths: bool,
+}
+
+impl<'a, I: FloatNum> Flatten<'a, I> {
+    /// Flatten `commands`, so that each line stays within `tolerance` of the curve it replaces.
+    /// The tolerance should be greater than zero.
+    #[inline]
+    pub fn new(commands: &'a [PathCommand<I>], tolerance: I) -> Self {
+        Self {
+            commands,
+            tolerance,
+            index: 0,
+            current: (I::zero(), I::zero()),
+            start: (I::zero(), I::zero()),
+            curve: None,
+            close_subpaths: false,
+        }
+    }
+
+    /// Move to `point`, returning the line there unless it has no length.
+    #[inline]
+    fn line_to(&mut self, point: Point<I>) -> Option<(Point<I>, Point<I>)> {
+        let start = replace(&mut self.current, point);
+
+        if start != point {
+            Some((start, point))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, I: FloatNum> Iterator for Flatten<'a, I> {
+    type Item = (Point<I>, Point<I>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.curve.as_mut().and_then(Iterator::next) {
+                if let Some(line) = self.line_to(point) {
+                    return Some(line);
+                }
+
+                continue;
+            }
+
+            let command = match self.commands.get(self.index) {
+                Some(&command) => command,
+                None if self.close_subpaths => {
+                    let start = self.start;
+                    return self.line_to(start);
+                }
+                None => return None,
+            };
+
+            // Fills close each subpath before starting the next one
+            if let PathCommand::MoveTo(_) = command {
+                if self.close_subpaths && self.current != self.start {
+                    let start = self.start;
+                    return self.line_to(start);
+                }
+            }
+
+            self.index += 1;
+
+            let line = match command {
+                PathCommand::MoveTo(point) => {
+                    self.current = point;
+                    self.start = point;
+                    None
+                }
+                PathCommand::LineTo(point) => self.line_to(point),
+                PathCommand::QuadTo(control, end) => {
+                    let points = [self.current, control, end, end];
+                    self.curve = Some(Curve::new(points, false, I::one(), self.tolerance));
+                    None
+                }
+                PathCommand::CubicTo(first, second, end) => {
+                    let points = [self.current, first, second, end];
+                    self.curve = Some(Curve::new(points, true, I::one(), self.tolerance));
+                    None
+                }
+                PathCommand::ConicTo(control, end, weight) => {
+                    let points = [self.current, control, end, end];
+                    self.curve = Some(Curve::new(points, false, weight, self.tolerance));
+                    None
+                }
+                PathCommand::Close => {
+                    let start = self.start;
+                    self.line_to(start)
+                }
+            };
+
+            if line.is_some() {
+                return line;
+            }
+        }
+    }
+}
+
+/// A flattened path can be filled, with any open subpaths closed.
+impl<'a, I: FloatNum> Edges<I> for Flatten<'a, I> {
+    #[inline]
+    fn edges<F: FnMut(Point<I>, Point<I>)>(&self, mut edge: F) {
+        let mut lines = self.clone();
+        lines.close_subpaths = true;
+
+        for (start, end) in lines {
+            edge(start, end);
+        }
+    }
+}
+
+/// The outline of a [`Path`], drawn with an aliased line algorithm.
+///
+/// The ends of each flattened line are rounded to the nearest point, and like a [`Polyline`],
+/// the points where the lines join up are only returned once. This includes the first point of a
+/// closed subpath.
+///
+/// [`Path`]: struct.Path.html
+/// [`Polyline`]: struct.Polyline.html
+pub struct PathStroke<'a, I: 'a, O> {
+    lines: Flatten<'a, I>,
+    algorithm: LineAlgorithm,
+    segment: Option<Segment<O>>,
+    end: Option<Point<I>>,
+    first: Option<Point<O>>,
+}
+
+impl<'a, I: FloatNum, O: SignedNum> PathStroke<'a, I, O> {
+    #[inline]
+    pub fn new(commands: &'a [PathCommand<I>], tolerance: I, algorithm: &LineAlgorithm) -> Self {
+        Self {
+            lines: Flatten::new(commands, tolerance),
+            algorithm: *algorithm,
+            segment: None,
+            end: None,
+            first: None,
+        }
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum> Iterator for PathStroke<'a, I, O> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.segment.as_mut().and_then(Iterator::next) {
+                return Some(point);
+            }
+
+            let (start, end) = self.lines.next()?;
+            let joined = self.end == Some(start);
+            self.end = Some(end);
+
+            let round = |point: Point<I>| (O::cast(point.0.round()), O::cast(point.1.round()));
+            let (start, end) = (round(start), round(end));
+            let mut segment = Segment::new(start, end, self.algorithm);
+
+            // Skip the point that the last line ended on, and the start of a closed subpath
+            if joined {
+                segment.next();
+
+                if self.first == Some(end) {
+                    segment.next_back();
+                }
+            } else {
+                self.first = Some(start);
+            }
+
+            self.segment = Some(segment);
+        }
+    }
+}
+
+/// The outline of a [`Path`], drawn with anti-aliased [`XiaolinWu`] lines.
+///
+/// Each line covers half of the pixel at each of its ends, so the pixels where lines join up are
+/// returned once for each line, and their coverage adds up to the same as the rest of the line.
+///
+/// [`Path`]: struct.Path.html
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+pub struct AntialiasedPathStroke<'a, I: 'a, O> {
+    lines: Flatten<'a, I>,
+    line: Option<XiaolinWu<I, O>>,
+}
+
+impl<'a, I: FloatNum, O: SignedNum> AntialiasedPathStroke<'a, I, O> {
+    #[inline]
+    pub fn new(commands: &'a [PathCommand<I>], tolerance: I) -> Self {
+        Self {
+            lines: Flatten::new(commands, tolerance),
+            line: None,
+        }
+    }
+}
+
+impl<'a, I: FloatNum, O: SignedNum> Iterator for AntialiasedPathStroke<'a, I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.line.as_mut().and_then(Iterator::next) {
+                return Some(item);
+            }
+
+            let (start, end) = self.lines.next()?;
+            self.line = Some(XiaolinWu::new(start, end));
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    let mut buffer = [PathCommand::Close; 8];
+    let mut path = Path::new(&mut buffer);
+    path.move_to((0.0, 0.0))
+        .line_to((3.0, 0.0))
+        .line_to((3.0, 3.0))
+        .line_to((0.0, 3.0))
+        .close();
+
+    assert_eq!(
+        path.stroke::<i32>(0.1, &LineAlgorithm::Bresenham)
+            .collect::<Vec<_>>(),
+        [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (2, 3),
+            (1, 3),
+            (0, 3),
+            (0, 2),
+            (0, 1)
+        ]
+    );
+
+    // Filling the path is the same as filling the polygon
+    let mut first = [0.0; 8];
+    let mut second = [0.0; 8];
+    let vertices = [(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0)];
+    assert!(path
+        .fill::<i32>(0.1, FillRule::NonZero, &mut first)
+        .eq(AntialiasedPolygonFill::new(
+            &vertices,
+            FillRule::NonZero,
+            &mut second
+        )));
+
+    // Curves are flattened to within the tolerance
+    let mut buffer = [PathCommand::Close; 2];
+    let mut path = Path::new(&mut buffer);
+    path.move_to((0.0, 0.0))
+        .quad_to((50.0, 100.0), (100.0, 0.0));
+
+    for &tolerance in &[1.0, 0.1] {
+        for (start, end) in path.flatten(tolerance) {
+            let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+            let t = middle.0 / 100.0;
+            let curve: f64 = 200.0 * t * (1.0 - t);
+            assert!((middle.1 - curve).abs() <= tolerance);
+        }
+    }
+
+    // A conic with the right weight follows a circle
+    let mut buffer = [PathCommand::Close; 2];
+    let mut path = Path::new(&mut buffer);
+    path.move_to((100.0, 0.0))
+        .conic_to((100.0, 100.0), (0.0, 100.0), 0.5f64.sqrt());
+
+    for (start, end) in path.flatten(0.1) {
+        assert!((start.0.hypot(start.1) - 100.0).abs() < 1e-9);
+        assert!((end.0.hypot(end.1) - 100.0).abs() < 1e-9);
+
+        let middle = ((start.0 + end.0) / 2.0, (start.1 + end.1) / 2.0);
+        assert!(100.0 - middle.0.hypot(middle.1) <= 0.1);
+    }
+}
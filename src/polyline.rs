@@ -0,0 +1,252 @@
+use {Bresenham, Point, SignedNum, Supercover, WalkGrid};
+use core::borrow::Borrow;
+use core::iter::Peekable;
+
+/// The line algorithm used to join the vertices of a [`Polyline`] or [`PolygonOutline`].
+///
+/// [`Polyline`]: struct.Polyline.html
+/// [`PolygonOutline`]: struct.PolygonOutline.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineAlgorithm {
+    /// Join the vertices with [`Bresenham`](struct.Bresenham.html) lines.
+    Bresenham,
+    /// Join the vertices with [`Supercover`](struct.Supercover.html) lines.
+    Supercover,
+    /// Join the vertices with [`WalkGrid`](struct.WalkGrid.html) lines.
+    WalkGrid,
+}
+
+/// A single line between two vertices.
+pub(crate) enum Segment<T> {
+    Bresenham(Bresenham<T>),
+    Supercover(Supercover<T>),
+    WalkGrid(WalkGrid<T>),
+}
+
+impl<T: SignedNum> Segment<T> {
+    #[inline]
+    pub(crate) fn new(start: Point<T>, end: Point<T>, algorithm: LineAlgorithm) -> Self {
+        match algorithm {
+            LineAlgorithm::Bresenham => Segment::Bresenham(Bresenham::new(start, end)),
+            LineAlgorithm::Supercover => Segment::Supercover(Supercover::new(start, end)),
+            LineAlgorithm::WalkGrid => Segment::WalkGrid(WalkGrid::new(start, end)),
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for Segment<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            Segment::Bresenham(ref mut line) => line.next(),
+            Segment::Supercover(ref mut line) => line.next(),
+            Segment::WalkGrid(ref mut line) => line.next(),
+        }
+    }
+}
+
+impl<T: SignedNum> DoubleEndedIterator for Segment<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match *self {
+            Segment::Bresenham(ref mut line) => line.next_back(),
+            Segment::Supercover(ref mut line) => line.next_back(),
+            Segment::WalkGrid(ref mut line) => line.next_back(),
+        }
+    }
+}
+
+/// A series of connected lines through a list of vertices.
+///
+/// The vertices can be given as a slice, or as any iterator of points or references to points.
+///
+/// Each line starts where the last one ended, so the shared point at each joint is only returned
+/// once. This makes it safe to use for XOR drawing or alpha blending, where drawing a point twice
+/// would show. Points where the lines cross each other away from the joints are still returned
+/// once for each line.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{LineAlgorithm, Polyline};
+///
+/// fn main() {
+///     let vertices = [(0, 0), (3, 0), (3, 2)];
+///
+///     for (x, y) in Polyline::new(&vertices, &LineAlgorithm::Bresenham) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 0), (3, 0), (3, 1), (3, 2),
+/// ```
+pub struct Polyline<T, I: Iterator> {
+    vertices: Peekable<I>,
+    algorithm: LineAlgorithm,
+    segment: Option<Segment<T>>,
+    last: Option<Point<T>>,
+    closing: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator> Polyline<T, I>
+where
+    I::Item: Borrow<Point<T>>,
+{
+    #[inline]
+    pub fn new<V>(vertices: V, algorithm: &LineAlgorithm) -> Self
+    where
+        V: IntoIterator<Item = I::Item, IntoIter = I>,
+    {
+        let mut vertices = vertices.into_iter().peekable();
+        let first = vertices.next().map(|first| *first.borrow());
+
+        Self {
+            vertices,
+            algorithm: *algorithm,
+            // Start with a line to the first vertex, so every line after it can skip its start
+            segment: first.map(|first| Segment::new(first, first, *algorithm)),
+            last: first,
+            closing: None,
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator> Iterator for Polyline<T, I>
+where
+    I::Item: Borrow<Point<T>>,
+{
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.segment.as_mut().and_then(Iterator::next) {
+                return Some(point);
+            }
+
+            let start = self.last?;
+            let (end, closing) = match self.vertices.next() {
+                // An outline that already ends back at the first vertex doesn't need another line
+                Some(end)
+                    if self.vertices.peek().is_none() && self.closing == Some(*end.borrow()) =>
+                {
+                    (self.closing.take()?, true)
+                }
+                Some(end) => (*end.borrow(), false),
+                None => (self.closing.take()?, true),
+            };
+
+            let mut segment = Segment::new(start, end, self.algorithm);
+            segment.next();
+
+            // The end of the closing line is the very first point
+            if closing {
+                segment.next_back();
+            }
+
+            self.segment = Some(segment);
+            self.last = Some(end);
+        }
+    }
+}
+
+/// The outline of a closed polygon.
+///
+/// This is like a [`Polyline`] that joins the last vertex back to the first, where the point at
+/// the first vertex is only returned once too.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{LineAlgorithm, PolygonOutline};
+///
+/// fn main() {
+///     let vertices = [(0, 0), (2, 0), (2, 2)];
+///
+///     for (x, y) in PolygonOutline::new(&vertices, &LineAlgorithm::WalkGrid) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (1, 1), (0, 1),
+/// ```
+///
+/// [`Polyline`]: struct.Polyline.html
+pub struct PolygonOutline<T, I: Iterator> {
+    polyline: Polyline<T, I>,
+}
+
+impl<T: SignedNum, I: Iterator> PolygonOutline<T, I>
+where
+    I::Item: Borrow<Point<T>>,
+{
+    #[inline]
+    pub fn new<V>(vertices: V, algorithm: &LineAlgorithm) -> Self
+    where
+        V: IntoIterator<Item = I::Item, IntoIter = I>,
+    {
+        let mut polyline = Polyline::new(vertices, algorithm);
+        polyline.closing = polyline.last;
+
+        Self { polyline }
+    }
+}
+
+impl<T: SignedNum, I: Iterator> Iterator for PolygonOutline<T, I>
+where
+    I::Item: Borrow<Point<T>>,
+{
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.polyline.next()
+    }
+}
+
+#[test]
+fn tests() {
+    let polyline = |vertices: &[Point<i32>], algorithm| {
+        Polyline::new(vertices, &algorithm).collect::<Vec<_>>()
+    };
+    let outline = |vertices: &[Point<i32>], algorithm| {
+        PolygonOutline::new(vertices, &algorithm).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        polyline(&[(0, 0), (2, 2), (4, 0)], LineAlgorithm::Bresenham),
+        [(0, 0), (1, 1), (2, 2), (3, 1), (4, 0)]
+    );
+
+    assert_eq!(
+        polyline(&[(0, 0), (1, 1)], LineAlgorithm::Supercover),
+        [(0, 0), (1, 1)]
+    );
+
+    assert_eq!(
+        outline(&[(0, 0), (2, 0), (2, 1), (0, 1)], LineAlgorithm::Bresenham),
+        [(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]
+    );
+
+    // Repeating the first vertex at the end doesn't repeat its point
+    assert_eq!(
+        outline(&[(0, 0), (2, 0), (0, 0)], LineAlgorithm::WalkGrid),
+        [(0, 0), (1, 0), (2, 0), (1, 0)]
+    );
+
+    assert_eq!(polyline(&[], LineAlgorithm::Bresenham), []);
+    assert_eq!(polyline(&[(3, 3)], LineAlgorithm::Bresenham), [(3, 3)]);
+    assert_eq!(outline(&[(3, 3)], LineAlgorithm::Bresenham), [(3, 3)]);
+    assert_eq!(
+        outline(&[(3, 3), (3, 3), (3, 3)], LineAlgorithm::Bresenham),
+        [(3, 3)]
+    );
+}
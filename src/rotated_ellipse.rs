@@ -0,0 +1,318 @@
+use {FloatNum, Point, SignedNum};
+use path::Curve;
+use core::cmp::Ordering;
+use core::f64::consts::{FRAC_PI_2, PI};
+use core::mem::replace;
+
+/// How far the flattened curves are allowed to stray from the real ellipse.
+const TOLERANCE: f64 = 0.1;
+
+/// How long the flattened lines can be, which is short enough that the ends of each line round to
+/// the same pixel or ones that are next to each other.
+const LENGTH: f64 = 0.5;
+
+/// An ellipse that can be turned to any angle, drawn as one 8-connected loop.
+///
+/// The ellipse is split at the points where it's furthest out along the x and y axes, which gives
+/// up to four pieces that only ever head in one direction along each axis. Pieces that cover more
+/// than a quarter of the way around are split in two, and each piece is a rational quadratic
+/// Bézier curve, which can follow an ellipse exactly. The curves are flattened into lines short
+/// enough that each one ends on the same pixel or the one next to it, and the ends of the lines
+/// are rounded to the nearest pixel.
+///
+/// A pixel at the corner of a step is left out when the pixels either side of it already touch,
+/// unless the curve passes through the diamond between the middles of its sides, which keeps the
+/// pointed ends of thin ellipses. This gives a loop without any gaps that doesn't bunch up into
+/// clumps of pixels.
+///
+/// The points are returned in order around the ellipse, and the point where the loop closes is
+/// only returned once. An ellipse with no size has no points, like a [`BresenhamCircle`] with a
+/// radius of zero.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::RotatedEllipse;
+///
+/// fn main() {
+///     let angle = std::f32::consts::FRAC_PI_4;
+///
+///     for (x, y) in RotatedEllipse::<f32, i8>::new((0.0, 0.0), 3.0, 1.0, angle) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (2, 2), (1, 2), (0, 1), (-1, 0), (-2, -1), (-2, -2), (-1, -2), (0, -1), (1, 0), (2, 1),
+/// ```
+///
+/// [`BresenhamCircle`]: struct.BresenhamCircle.html
+pub struct RotatedEllipse<I, O> {
+    lines: Pieces<I>,
+    first: Option<Point<O>>,
+    previous: Option<Point<O>>,
+    pending: Option<(Point<O>, bool)>,
+}
+
+impl<I: FloatNum, O: SignedNum> RotatedEllipse<I, O> {
+    /// Make an ellipse with radii along its own axes, turned by `angle` radians from the x axis
+    /// towards the y axis.
+    #[inline]
+    pub fn new(center: Point<I>, radius_x: I, radius_y: I, angle: I) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::from_parallelogram(
+            center,
+            (radius_x * cos, radius_x * sin),
+            (-radius_y * sin, radius_y * cos),
+        )
+    }
+
+    /// Make the ellipse that fits inside the parallelogram with corners at `center`, plus or
+    /// minus `first` and plus or minus `second`. The ellipse touches the middle of each side,
+    /// at `center` plus or minus each of the offsets.
+    #[inline]
+    pub fn from_parallelogram(center: Point<I>, first: Point<I>, second: Point<I>) -> Self {
+        Self {
+            lines: Pieces::new(center, first, second),
+            first: None,
+            previous: None,
+            pending: None,
+        }
+    }
+}
+
+/// Check if two points are the same or next to each other, including diagonally.
+#[inline]
+fn touching<O: SignedNum>(a: Point<O>, b: Point<O>) -> bool {
+    (a.0 - b.0).abs() <= O::one() && (a.1 - b.1).abs() <= O::one()
+}
+
+/// Check if a line passes within half a pixel of the middle of `pixel`, measured along the axes.
+#[inline]
+fn centered<I: FloatNum, O: SignedNum>(start: Point<I>, end: Point<I>, pixel: Point<O>) -> bool {
+    let (x, y) = (I::cast(pixel.0), I::cast(pixel.1));
+    let (start, end) = ((start.0 - x, start.1 - y), (end.0 - x, end.1 - y));
+    let distance = |t: I| {
+        let t = t.max(I::zero()).min(I::one());
+        (start.0 + (end.0 - start.0) * t).abs() + (start.1 + (end.1 - start.1) * t).abs()
+    };
+
+    // The distance is smallest at one of the ends, or where the line crosses one of the axes
+    let half = I::cast(0.5);
+    distance(I::zero()) <= half
+        || distance(I::one()) <= half
+        || (start.0 != end.0 && distance(start.0 / (start.0 - end.0)) <= half)
+        || (start.1 != end.1 && distance(start.1 / (start.1 - end.1)) <= half)
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for RotatedEllipse<I, O> {
+    type Item = Point<O>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let round = |point: Point<I>| (O::cast(point.0.round()), O::cast(point.1.round()));
+
+        // Hold on to each pixel until the next one is known, so that corners can be left out
+        for (start, end) in self.lines.by_ref() {
+            let (pending, was_centered) = match self.pending {
+                Some(pending) => pending,
+                None => (round(start), false),
+            };
+
+            let pixel = round(end);
+            let pending_centered = was_centered || centered(start, end, pending);
+
+            if pixel == pending {
+                self.pending = Some((pending, pending_centered));
+                continue;
+            }
+
+            self.pending = Some((pixel, centered(start, end, pixel)));
+
+            // Leaving out a corner at a thin tip, where the line turns back on itself, would
+            // return the pixel before it twice in a row
+            match self.previous {
+                Some(previous) if previous == pending => continue,
+                Some(previous)
+                    if previous != pixel && !pending_centered && touching(previous, pixel) =>
+                {
+                    continue
+                }
+                None => self.first = Some(pending),
+                _ => {}
+            }
+
+            self.previous = Some(pending);
+            return Some(pending);
+        }
+
+        // The loop ends back on the first pixel, which has already been returned
+        let (pending, _) = self.pending.take()?;
+
+        if self.first == Some(pending) || self.previous == Some(pending) {
+            None
+        } else {
+            Some(pending)
+        }
+    }
+}
+
+/// The flattened lines of an ellipse, made from pieces that each cover up to a quarter of it.
+struct Pieces<I> {
+    /// The control point, end point and weight of each piece.
+    pieces: [(Point<I>, Point<I>, I); 8],
+    count: usize,
+    index: usize,
+    current: Point<I>,
+    curve: Option<Curve<I>>,
+}
+
+impl<I: FloatNum> Pieces<I> {
+    #[inline]
+    fn new(center: Point<I>, first: Point<I>, second: Point<I>) -> Self {
+        let turn = I::cast(2.0 * PI);
+        let at = |angle: I| {
+            let (sin, cos) = angle.sin_cos();
+            (
+                first.0 * cos + second.0 * sin,
+                first.1 * cos + second.1 * sin,
+            )
+        };
+
+        // Find the angles where the ellipse is furthest out along each axis, and sort them
+        let mut angles = [I::zero(); 4];
+        for (i, &(a, b)) in [(first.0, second.0), (first.1, second.1)]
+            .iter()
+            .enumerate()
+        {
+            let angle = b.atan2(a);
+            angles[i * 2] = if angle < I::zero() {
+                angle + turn
+            } else {
+                angle
+            };
+            angles[i * 2 + 1] = angles[i * 2] + I::cast(PI);
+        }
+
+        for angle in &mut angles {
+            if *angle >= turn {
+                *angle -= turn;
+            }
+        }
+
+        angles.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let start = at(angles[0]);
+        let mut pieces = Pieces {
+            pieces: [((I::zero(), I::zero()), (I::zero(), I::zero()), I::zero()); 8],
+            count: 0,
+            index: 0,
+            current: (center.0 + start.0, center.1 + start.1),
+            curve: None,
+        };
+
+        for i in 0..4 {
+            let from = angles[i];
+            let to = if i < 3 {
+                angles[i + 1]
+            } else {
+                angles[0] + turn
+            };
+
+            let parts = if to - from > I::cast(FRAC_PI_2) { 2 } else { 1 };
+            let step = (to - from) / I::cast(parts);
+
+            for part in 0..parts {
+                // The control point is where the tangents at either end meet
+                let half = step * I::cast(0.5);
+                let middle = at(from + step * I::cast(part) + half);
+                let end = at(from + step * I::cast(part + 1));
+                let weight = half.cos();
+
+                pieces.pieces[pieces.count] = (
+                    (center.0 + middle.0 / weight, center.1 + middle.1 / weight),
+                    (center.0 + end.0, center.1 + end.1),
+                    weight,
+                );
+                pieces.count += 1;
+            }
+        }
+
+        // Close the loop exactly where it started
+        pieces.pieces[pieces.count - 1].1 = pieces.current;
+        pieces
+    }
+}
+
+impl<I: FloatNum> Iterator for Pieces<I> {
+    type Item = (Point<I>, Point<I>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(point) = self.curve.as_mut().and_then(Iterator::next) {
+                let start = replace(&mut self.current, point);
+
+                if start != point {
+                    return Some((start, point));
+                }
+
+                continue;
+            }
+
+            if self.index == self.count {
+                return None;
+            }
+
+            let (control, end, weight) = self.pieces[self.index];
+            let points = [self.current, control, end, end];
+            let curve = Curve::new(points, false, weight, I::cast(TOLERANCE));
+            self.curve = Some(curve.max_length(I::cast(LENGTH)));
+            self.index += 1;
+        }
+    }
+}
+
+#[test]
+fn tests() {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    let ellipse = |radius_x, radius_y, angle| {
+        RotatedEllipse::<f64, i32>::new((0.0, 0.0), radius_x, radius_y, angle).collect::<Vec<_>>()
+    };
+
+    // Without any turn it's the same as an axis-aligned ellipse, in its own order
+    let mut points = ellipse(2.0, 1.0, 0.0);
+    points.sort();
+    assert_eq!(
+        points,
+        [
+            (-2, 0),
+            (-1, -1),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 1),
+            (2, 0)
+        ]
+    );
+
+    // Turning by a quarter swaps the axes, and a parallelogram can give the same ellipse
+    let mut turned = ellipse(1.0, 2.0, FRAC_PI_2);
+    turned.sort();
+    assert_eq!(turned, points);
+
+    let parallelogram =
+        RotatedEllipse::<f64, i32>::from_parallelogram((0.0, 0.0), (2.0, 0.0), (0.0, 1.0));
+    assert_eq!(parallelogram.collect::<Vec<_>>(), ellipse(2.0, 1.0, 0.0));
+
+    assert_eq!(ellipse(0.0, 0.0, 1.0), []);
+
+    // The same pixel isn't returned twice in a row at a thin tip
+    let thin = ellipse(12.0, 1.0, FRAC_PI_4);
+    assert!(thin.windows(2).all(|pair| pair[0] != pair[1]));
+}
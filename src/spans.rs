@@ -0,0 +1,141 @@
+//! An iterator that returns runs of pixels from a line.
+
+use {Point, SignedNum};
+
+/// The axis that a [`Spans`] run lies along.
+///
+/// [`Spans`]: struct.Spans.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// The run is horizontal, so only the x value changes.
+    X,
+    /// The run is vertical, so only the y value changes.
+    Y,
+}
+
+/// An iterator that returns `(start, length, axis)` tuples for each run of pixels in a line.
+///
+/// A run is a group of neighbouring pixels along the line's major axis, so a shallow line gives
+/// horizontal runs and a steep line gives vertical ones. `start` is the end of the run with the
+/// smallest value along `axis`, no matter which way the line was drawn, so a horizontal run covers
+/// `start.0..start.0 + length` on row `start.1`. The runs come in the same order as the points and
+/// cover exactly the same pixels.
+///
+/// [`Bresenham`], [`SymmetricBresenham`] and [`Midpoint`] have a `spans()` function to turn them
+/// into a [`Spans`] iterator.
+///
+/// Example using [`Bresenham`]:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::Bresenham;
+///
+/// fn main() {
+///     for (start, length, axis) in Bresenham::new((0, 0), (7, 2)).spans() {
+///         println!("{:?} {} {:?}", start, length, axis);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0) 4 X
+/// (4, 1) 3 X
+/// (7, 2) 1 X
+/// ```
+///
+/// [`Spans`]: struct.Spans.html
+/// [`Bresenham`]: ../struct.Bresenham.html
+/// [`SymmetricBresenham`]: ../struct.SymmetricBresenham.html
+/// [`Midpoint`]: ../struct.Midpoint.html
+pub struct Spans<T, I> {
+    iterator: I,
+    axis: Axis,
+    next: Option<Point<T>>,
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Spans<T, I> {
+    /// Group the points of `iterator` into runs along `axis`.
+    #[inline]
+    pub fn new(iterator: I, axis: Axis) -> Self {
+        Self {
+            iterator,
+            axis,
+            next: None,
+        }
+    }
+
+    /// Check if `point` carries on the run that ended at `last`.
+    #[inline]
+    fn continues(&self, last: Point<T>, point: Point<T>) -> bool {
+        match self.axis {
+            Axis::X => point.1 == last.1 && (point.0 - last.0).abs() == T::one(),
+            Axis::Y => point.0 == last.0 && (point.1 - last.1).abs() == T::one(),
+        }
+    }
+}
+
+impl<T: SignedNum, I: Iterator<Item = Point<T>>> Iterator for Spans<T, I> {
+    type Item = (Point<T>, usize, Axis);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.next.take() {
+            Some(point) => point,
+            None => self.iterator.next()?,
+        };
+
+        let mut start = first;
+        let mut last = first;
+        let mut length = 1;
+
+        while let Some(point) = self.iterator.next() {
+            if !self.continues(last, point) {
+                self.next = Some(point);
+                break;
+            }
+
+            if point < start {
+                start = point;
+            }
+
+            last = point;
+            length += 1;
+        }
+
+        Some((start, length, self.axis))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iterator.size_hint();
+        let extra = self.next.is_some() as usize;
+
+        ((lower + extra).min(1), upper.map(|upper| upper + extra))
+    }
+}
+
+#[test]
+fn spans() {
+    use {Bresenham, Midpoint};
+
+    assert_eq!(
+        Bresenham::new((0, 0), (-7, 2)).spans().collect::<Vec<_>>(),
+        [
+            ((-3, 0), 4, Axis::X),
+            ((-6, 1), 3, Axis::X),
+            ((-7, 2), 1, Axis::X)
+        ]
+    );
+
+    assert_eq!(
+        Midpoint::new((0.0, 0.0), (1.0, 4.0))
+            .spans()
+            .collect::<Vec<_>>(),
+        [((0, 0), 2, Axis::Y), ((1, 2), 3, Axis::Y)]
+    );
+
+    assert_eq!(
+        Bresenham::new((2, 2), (2, 2)).spans().collect::<Vec<_>>(),
+        [((2, 2), 1, Axis::X)]
+    );
+}
@@ -31,9 +31,16 @@
 ///
 /// [`Steps`]: struct.Steps.html
 /// [`WalkGrid`]: ../struct.WalkGrid.html
+///
+/// If the underlying iterator is a [`DoubleEndedIterator`] or an [`ExactSizeIterator`], so is
+/// [`Steps`].
+///
+/// [`DoubleEndedIterator`]: https://doc.rust-lang.org/core/iter/trait.DoubleEndedIterator.html
+/// [`ExactSizeIterator`]: https://doc.rust-lang.org/core/iter/trait.ExactSizeIterator.html
 pub struct Steps<T, I> {
     iterator: I,
     prev: Option<T>,
+    back: Option<T>,
 }
 
 impl<T: Copy, I: Iterator<Item = T>> Steps<T, I> {
@@ -41,6 +48,7 @@ impl<T: Copy, I: Iterator<Item = T>> Steps<T, I> {
     pub fn new(mut iterator: I) -> Self {
         Self {
             prev: iterator.next(),
+            back: None,
             iterator,
         }
     }
@@ -51,15 +59,51 @@ impl<T: Copy, I: Iterator<Item = T>> Iterator for Steps<T, I> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iterator.next().and_then(|next| {
-            self.prev.map(|prev| {
-                self.prev = Some(next);
-                (prev, next)
-            })
+        // Once the iterator runs dry, the last point may already have been taken from the back
+        let next = match self.iterator.next() {
+            Some(next) => next,
+            None => self.back.take()?,
+        };
+
+        self.prev.map(|prev| {
+            self.prev = Some(next);
+            (prev, next)
         })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iterator.size_hint();
+        let extra = self.prev.is_some() as usize + self.back.is_some() as usize;
+
+        (
+            (lower + extra).saturating_sub(1),
+            upper.map(|upper| (upper + extra).saturating_sub(1)),
+        )
+    }
+}
+
+impl<T: Copy, I: DoubleEndedIterator<Item = T>> DoubleEndedIterator for Steps<T, I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let back = match self.back.take() {
+            Some(back) => back,
+            None => self.iterator.next_back()?,
+        };
+
+        match self.iterator.next_back() {
+            Some(prev) => {
+                self.back = Some(prev);
+                Some((prev, back))
+            }
+            // Otherwise the only point left is the one at the front
+            None => self.prev.take().map(|prev| (prev, back)),
+        }
+    }
 }
 
+impl<T: Copy, I: ExactSizeIterator<Item = T>> ExactSizeIterator for Steps<T, I> {}
+
 #[test]
 fn steps() {
     use Midpoint;
@@ -76,3 +120,27 @@ fn steps() {
         ]
     );
 }
+
+#[test]
+fn steps_double_ended() {
+    use Bresenham;
+
+    let steps = Bresenham::new((0, 0), (4, 2)).steps();
+    assert_eq!(steps.len(), 4);
+
+    let mut reversed = steps.rev().collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(
+        reversed,
+        Bresenham::new((0, 0), (4, 2)).steps().collect::<Vec<_>>()
+    );
+
+    let mut steps = Bresenham::new((0, 0), (4, 2)).steps();
+    assert_eq!(steps.next(), Some(((0, 0), (1, 0))));
+    assert_eq!(steps.next_back(), Some(((3, 1), (4, 2))));
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps.next_back(), Some(((2, 1), (3, 1))));
+    assert_eq!(steps.next(), Some(((1, 0), (2, 1))));
+    assert_eq!(steps.next(), None);
+    assert_eq!(steps.next_back(), None);
+}
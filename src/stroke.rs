@@ -0,0 +1,874 @@
+use {AntialiasedPolygonFill, Edges, FillRule, FloatNum, Point, PolygonFill, SignedNum};
+
+/// The shape of the ends of a [`StrokeOutline`].
+///
+/// [`StrokeOutline`]: struct.StrokeOutline.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrokeCap {
+    /// The stroke stops exactly at the first and last vertices.
+    Butt,
+    /// The stroke ends in a half circle around the first and last vertices.
+    Round,
+    /// The stroke carries on past the first and last vertices by half of its width.
+    Square,
+}
+
+/// The shape of the corners of a [`StrokeOutline`], where one line meets the next.
+///
+/// [`StrokeOutline`]: struct.StrokeOutline.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StrokeJoin<I> {
+    /// The outer edges are carried on until they meet in a point, unless the point is further
+    /// from the vertex than this many times the width, in which case the corner is bevelled.
+    ///
+    /// The length is measured the same way as `stroke-miterlimit` in SVG, so a limit of `4.0`
+    /// bevels corners that are sharper than about 29 degrees.
+    Miter(I),
+    /// The corner is rounded off with an arc around the vertex.
+    Round,
+    /// The corner is cut off with a straight line between the outer edges.
+    Bevel,
+}
+
+/// How the outline turns around a vertex.
+#[derive(Clone, Copy)]
+enum Turn<I> {
+    /// The lines carry on in the same direction, so only one point is needed.
+    Straight,
+    /// The outline is on the inside of the corner, and cuts across it at this offset from the
+    /// vertex where the two sides meet.
+    Cross(Point<I>),
+    /// The outline is on the inside of the corner, but one of the lines is too short for the two
+    /// sides to meet, so it goes around the outlines of the vertices either side instead.
+    Inner,
+    /// The outline is cut straight across.
+    Bevel,
+    /// The outline meets in a point at this offset from the vertex.
+    Miter(Point<I>),
+    /// The outline goes around a square end.
+    Square,
+    /// The outline goes around an arc of this many pieces.
+    Round(usize),
+}
+
+/// A vertex of the outline, and the way the outline turns around it.
+#[derive(Clone, Copy)]
+struct Corner<I> {
+    vertex: Point<I>,
+    /// The direction the outline was heading towards the vertex.
+    direction: Point<I>,
+    /// The offset of the outline from the vertex along the line coming in, and going out.
+    before: Point<I>,
+    after: Point<I>,
+    turn: Turn<I>,
+    /// The points from `first` up to `end` are used, which leaves out the ones that are inside an
+    /// inner corner next to this one.
+    first: usize,
+    end: usize,
+}
+
+impl<I: FloatNum> Corner<I> {
+    /// Get the number of points that the outline has around the vertex.
+    #[inline]
+    fn len(&self) -> usize {
+        match self.turn {
+            Turn::Straight | Turn::Cross(_) => 1,
+            Turn::Bevel => 2,
+            Turn::Inner | Turn::Miter(_) => 3,
+            Turn::Square => 4,
+            Turn::Round(pieces) => pieces + 1,
+        }
+    }
+
+    /// Get one of the points that the outline has around the vertex.
+    #[inline]
+    fn point(&self, index: usize) -> Point<I> {
+        let offset = match (self.turn, index) {
+            (Turn::Cross(offset), _) => offset,
+            (_, 0) => self.before,
+            (Turn::Inner, 1) => (I::zero(), I::zero()),
+            (Turn::Miter(miter), 1) => miter,
+            (Turn::Square, 1) => add(self.before, self.direction),
+            (Turn::Square, 2) => add(self.after, self.direction),
+            (Turn::Round(pieces), _) if index < pieces => {
+                // Turn the offset clockwise, towards the way the outline is heading
+                let angle = angle(self.before, self.after) * I::cast(index) / I::cast(pieces);
+                let (sin, cos) = angle.sin_cos();
+                (
+                    self.before.0 * cos + self.before.1 * sin,
+                    self.before.1 * cos - self.before.0 * sin,
+                )
+            }
+            _ => self.after,
+        };
+
+        add(self.vertex, offset)
+    }
+}
+
+/// The outline around the inside of an inner corner.
+///
+/// This comes in along the line to the vertex from the outline around the vertex before, and goes
+/// back out along the next line to the outline around the vertex after. Going through the vertex
+/// would cover the stroke twice where the two overlap, so the outline follows whichever of them is
+/// on the outside instead, switching over each time they cross.
+#[derive(Clone, Copy)]
+struct Inside<I> {
+    before: Corner<I>,
+    corner: Corner<I>,
+    after: Corner<I>,
+    /// The first point used from the corner before, and the number of points used from the
+    /// corner after. Inner corners either side only add the ends of the lines they share.
+    from: usize,
+    to: usize,
+}
+
+/// A position along the way in or out of an [`Inside`], and the segment of the other way that
+/// it crossed over to get there.
+///
+/// [`Inside`]: struct.Inside.html
+#[derive(Clone, Copy)]
+struct Trace<I> {
+    outgoing: bool,
+    segment: usize,
+    along: I,
+    crossed: Option<usize>,
+}
+
+/// The points that the outline around the inside of an inner corner uses, as the end of the
+/// points used from the corner before, the points used from the trace, and the first of the
+/// points used from the corner after.
+struct Cut {
+    before: usize,
+    first: usize,
+    end: usize,
+    after: usize,
+}
+
+impl<I: FloatNum> Inside<I> {
+    /// Get the number of points on the way in or out.
+    #[inline]
+    fn len(&self, outgoing: bool) -> usize {
+        if outgoing {
+            self.to + 2
+        } else {
+            self.before.len() - self.from + 2
+        }
+    }
+
+    /// Get a point on the way in, which ends at the vertex, or on the way out, which starts there.
+    #[inline]
+    fn point(&self, outgoing: bool, index: usize) -> Point<I> {
+        let count = self.before.len() - self.from;
+
+        match (outgoing, index) {
+            (true, 0) => self.corner.vertex,
+            (true, 1) => self.corner.point(2),
+            (true, _) => self.after.point(index - 2),
+            (false, _) if index < count => self.before.point(self.from + index),
+            (false, _) if index == count => self.corner.point(0),
+            (false, _) => self.corner.vertex,
+        }
+    }
+
+    /// Check whether heading in `direction` through one of the points on the way in or out goes
+    /// into the stroke on the inside of it.
+    #[inline]
+    fn enters(&self, outgoing: bool, index: usize, direction: Point<I>) -> bool {
+        let point = self.point(outgoing, index);
+        let into = |side: Point<I>| cross(direction, side) >= I::zero();
+
+        let before = index
+            .checked_sub(1)
+            .map(|index| sub(point, self.point(outgoing, index)));
+        let after = if index + 1 < self.len(outgoing) {
+            Some(sub(self.point(outgoing, index + 1), point))
+        } else {
+            None
+        };
+
+        match (before, after) {
+            // The inside of a corner that turns away from the stroke is on the inside of both sides
+            (Some(before), Some(after)) if cross(before, after) <= I::zero() => {
+                into(before) && into(after)
+            }
+            (Some(before), Some(after)) => into(before) || into(after),
+            (Some(side), None) | (None, Some(side)) => into(side),
+            (None, None) => false,
+        }
+    }
+
+    /// Get the position at the start of the way in.
+    #[inline]
+    fn start(&self) -> Trace<I> {
+        Trace {
+            outgoing: false,
+            segment: 0,
+            along: -I::one(),
+            crossed: None,
+        }
+    }
+
+    /// Get the next point of the outline after `trace`, along with its position.
+    #[inline]
+    fn step(&self, mut trace: Trace<I>) -> Option<(Point<I>, Trace<I>)> {
+        loop {
+            let (outgoing, segment) = (trace.outgoing, trace.segment);
+            let (length, other) = (self.len(outgoing), self.len(!outgoing));
+
+            if segment + 1 >= length {
+                return None;
+            }
+
+            let (a, b) = (
+                self.point(outgoing, segment),
+                self.point(outgoing, segment + 1),
+            );
+            let mut nearest = None;
+
+            for crossed in 0..other - 1 {
+                // The lines across the ends of the two lines meet at the vertex without crossing
+                let ends = if outgoing {
+                    segment == 0 && crossed == other - 2
+                } else {
+                    segment == length - 2 && crossed == 0
+                };
+
+                if ends || trace.crossed == Some(crossed) {
+                    continue;
+                }
+
+                let (c, d) = (
+                    self.point(!outgoing, crossed),
+                    self.point(!outgoing, crossed + 1),
+                );
+
+                if let Some((t, u)) = crossing(a, b, c, d) {
+                    // Points where the other way turns are looked at along with both sides of them
+                    let (enters, segment, along) = if u > I::zero() && u < I::one() {
+                        (cross(sub(b, a), sub(d, c)) > I::zero(), crossed, u)
+                    } else {
+                        let index = if u.is_zero() { crossed } else { crossed + 1 };
+                        let enters = self.enters(!outgoing, index, sub(b, a));
+
+                        if index + 1 < other {
+                            (enters, index, I::zero())
+                        } else {
+                            (enters, index - 1, I::one())
+                        }
+                    };
+
+                    let nearer = match nearest {
+                        Some((nearest, _, _)) => t < nearest,
+                        None => true,
+                    };
+
+                    if enters && t > trace.along && nearer {
+                        nearest = Some((t, segment, along));
+                    }
+                }
+            }
+
+            if let Some((t, crossed, along)) = nearest {
+                let point = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+                let trace = Trace {
+                    outgoing: !outgoing,
+                    segment: crossed,
+                    along,
+                    crossed: Some(segment),
+                };
+
+                return Some((point, trace));
+            }
+
+            // Carry on out from the vertex at the end of the way in
+            let out = !outgoing && segment + 2 == length;
+            let next = Trace {
+                outgoing: outgoing || out,
+                segment: if out { 0 } else { segment + 1 },
+                along: I::zero(),
+                crossed: None,
+            };
+
+            // A crossing at the end of a segment has already given its point
+            if trace.along < I::one() {
+                return Some((b, next));
+            }
+
+            trace = next;
+        }
+    }
+
+    /// Follow the outline all the way through, to work out which points to use from the corners
+    /// either side and from the trace, or `None` if it goes around in circles.
+    #[inline]
+    fn cut(&self) -> Option<Cut> {
+        let count = self.before.len() - self.from;
+        let mut trace = self.start();
+        let (mut first, mut end) = (None, None);
+
+        for index in 0..4 * (self.len(false) + self.len(true)) {
+            trace = match self.step(trace) {
+                Some((_, trace)) => trace,
+                None => {
+                    let (first, (end, after)) = (first?, end?);
+
+                    return Some(Cut {
+                        before: self.from + 1 + first,
+                        first,
+                        end,
+                        after,
+                    });
+                }
+            };
+
+            // The points on the way in before anything crosses are left to the corner before
+            if first.is_none()
+                && (trace.crossed.is_some() || trace.outgoing || trace.segment >= count)
+            {
+                first = Some(index);
+            }
+
+            // The points on the way out after the last crossing are left to the corner after
+            if trace.crossed.is_some() {
+                end = None;
+            } else if end.is_none() && trace.outgoing && trace.segment >= 2 {
+                end = Some((index, trace.segment - 2));
+            }
+        }
+
+        None
+    }
+}
+
+/// The outline of a polyline drawn with a configurable width, which can be filled to draw it.
+///
+/// Stacking thick lines on top of each other leaves notches where they meet, so this works out
+/// one outline that goes all the way around the polyline instead. The ends are shaped by a
+/// [`StrokeCap`] and the corners by a [`StrokeJoin`], with round ones split up into straight
+/// lines that stay within `tolerance` of the real arc.
+///
+/// The outline is returned as the vertices of a polygon, which goes along one side of the
+/// polyline and back along the other. Where a line is too short for the sides to meet on the
+/// inside of a corner, the outline goes around the outside of the ends of the lines either side
+/// instead. Where the polyline doubles back over itself the outline still crosses over itself, so
+/// it has to be filled with the [`NonZero`] rule, which [`fill`] and [`fill_antialiased`] both
+/// use. A polyline with only one vertex is drawn as a dot, which is empty for butt caps.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{StrokeCap, StrokeJoin, StrokeOutline};
+///
+/// fn main() {
+///     let vertices = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)];
+///     let join = StrokeJoin::Miter(4.0);
+///     let outline = StrokeOutline::new(&vertices, 2.0, StrokeCap::Butt, join, 0.1);
+///     let mut buffer = [(0, 0); 16];
+///
+///     for (start, length) in outline.fill::<i8>(&mut buffer) {
+///         println!("{:?} {}", start, length);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, -1) 6
+/// (0, 0) 6
+/// (0, 1) 6
+/// (3, 2) 3
+/// (3, 3) 3
+/// (3, 4) 3
+/// ```
+///
+/// [`StrokeCap`]: enum.StrokeCap.html
+/// [`StrokeJoin`]: enum.StrokeJoin.html
+/// [`NonZero`]: enum.FillRule.html#variant.NonZero
+/// [`fill`]: #method.fill
+/// [`fill_antialiased`]: #method.fill_antialiased
+#[derive(Clone)]
+pub struct StrokeOutline<'a, I: 'a> {
+    vertices: &'a [Point<I>],
+    half_width: I,
+    cap: StrokeCap,
+    join: StrokeJoin<I>,
+    tolerance: I,
+    visit: usize,
+    visits: usize,
+    index: usize,
+    corner: Option<Corner<I>>,
+    inside: Option<(Inside<I>, Trace<I>)>,
+}
+
+impl<'a, I: FloatNum> StrokeOutline<'a, I> {
+    #[inline]
+    pub fn new(
+        vertices: &'a [Point<I>],
+        width: I,
+        cap: StrokeCap,
+        join: StrokeJoin<I>,
+        tolerance: I,
+    ) -> Self {
+        // The outline visits every vertex on the way along the polyline, and all but the ends
+        // again on the way back
+        let visits = match vertices.len() {
+            0 => 0,
+            1 => 2,
+            length => 2 * (length - 1),
+        };
+
+        Self {
+            vertices,
+            half_width: width.abs() * I::cast(0.5),
+            cap,
+            join,
+            tolerance,
+            visit: 0,
+            visits,
+            index: 0,
+            corner: None,
+            inside: None,
+        }
+    }
+
+    /// Fill the outline with a [`PolygonFill`], after rounding it to whole pixels and storing it in
+    /// `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the rounded outline doesn't fit in the buffer.
+    ///
+    /// [`PolygonFill`]: struct.PolygonFill.html
+    #[inline]
+    pub fn fill<O: SignedNum>(self, buffer: &mut [Point<O>]) -> PolygonFill<'_, O> {
+        let mut length = 0;
+
+        for point in self {
+            let point = (O::cast(point.0.round()), O::cast(point.1.round()));
+
+            if length == 0 || buffer[length - 1] != point {
+                assert!(length < buffer.len(), "the buffer is too small");
+                buffer[length] = point;
+                length += 1;
+            }
+        }
+
+        PolygonFill::new(&buffer[..length], FillRule::NonZero)
+    }
+
+    /// Fill the outline with an [`AntialiasedPolygonFill`], which adds up the edges in `buffer`.
+    ///
+    /// The coverage is exact as long as the polyline doesn't double back over itself, including at
+    /// corners where the lines are too short for the sides to meet. Where it does double back, the
+    /// outline goes around that part more than once, and the edges there are added up more than
+    /// once before the coverage is clamped. This makes pixels along the edges of the overlap too
+    /// bright, so the total is more than the area of the stroke.
+    ///
+    /// [`AntialiasedPolygonFill`]: struct.AntialiasedPolygonFill.html
+    #[inline]
+    pub fn fill_antialiased<'b, O: SignedNum>(
+        self,
+        buffer: &'b mut [I],
+    ) -> AntialiasedPolygonFill<'b, I, O, Self> {
+        AntialiasedPolygonFill::with_edges(self, FillRule::NonZero, buffer)
+    }
+
+    /// Get the unit vector from the vertex at `index` to the next one that's in a different place,
+    /// looking forwards or backwards through the vertices, along with the distance to it.
+    #[inline]
+    fn direction(&self, index: usize, forwards: bool) -> Option<(Point<I>, I)> {
+        let vertex = self.vertices[index];
+        let next = if forwards {
+            self.vertices[index + 1..]
+                .iter()
+                .find(|&&next| next != vertex)
+        } else {
+            self.vertices[..index]
+                .iter()
+                .rev()
+                .find(|&&next| next != vertex)
+        }?;
+
+        let delta = (next.0 - vertex.0, next.1 - vertex.1);
+        let length = delta.0.hypot(delta.1);
+        Some(((delta.0 / length, delta.1 / length), length))
+    }
+
+    /// Work out how the outline goes around the vertex for a visit, or `None` if the vertex is in
+    /// the same place as one next to it and has already been visited.
+    #[inline]
+    fn corner(&self, visit: usize) -> Option<Corner<I>> {
+        let last = self.visits / 2;
+        let (index, forwards) = if visit <= last {
+            (visit.min(self.vertices.len() - 1), visit > 0)
+        } else {
+            (2 * last - visit, false)
+        };
+
+        let vertex = self.vertices[index];
+        let is_end = visit == 0 || visit == last;
+
+        let (direction, next, shortest) = if is_end {
+            // Turn around, treating a single point as if it were horizontal
+            let direction = match self.direction(index, !forwards) {
+                Some((back, _)) => (-back.0, -back.1),
+                None if forwards => (I::one(), I::zero()),
+                None => (-I::one(), I::zero()),
+            };
+
+            (direction, (-direction.0, -direction.1), I::zero())
+        } else {
+            let (back, back_length) = self.direction(index, !forwards)?;
+            let previous = if forwards { index - 1 } else { index + 1 };
+
+            if self.vertices[previous] == vertex {
+                return None;
+            }
+
+            let (next, next_length) = self.direction(index, forwards)?;
+            ((-back.0, -back.1), next, back_length.min(next_length))
+        };
+
+        let half_width = self.half_width;
+        let before = (-direction.1 * half_width, direction.0 * half_width);
+        let after = (-next.1 * half_width, next.0 * half_width);
+        let direction = (direction.0 * half_width, direction.1 * half_width);
+
+        let cross = before.0 * after.1 - before.1 * after.0;
+        let dot = before.0 * after.0 + before.1 * after.1;
+
+        let round = || {
+            let tolerance = self.tolerance.min(half_width);
+            let step = I::cast(2) * (I::one() - tolerance / half_width).acos();
+            let pieces = (angle(before, after) / step).ceil().to_usize().unwrap_or(1);
+            Turn::Round(pieces.max(1))
+        };
+
+        let turn = if is_end {
+            match self.cap {
+                StrokeCap::Butt => Turn::Bevel,
+                StrokeCap::Round => round(),
+                StrokeCap::Square => Turn::Square,
+            }
+        } else if cross > I::zero() {
+            // The sides meet this far along each line, which is fine as long as it doesn't reach
+            // past the middle of either of them
+            let cosine = dot / (half_width * half_width);
+            let distance = half_width * ((I::one() - cosine) / (I::one() + cosine)).sqrt();
+
+            if distance * I::cast(2) <= shortest {
+                let scale = (I::one() + cosine).recip();
+                Turn::Cross(((before.0 + after.0) * scale, (before.1 + after.1) * scale))
+            } else {
+                Turn::Inner
+            }
+        } else if cross.is_zero() && dot > I::zero() {
+            Turn::Straight
+        } else {
+            match self.join {
+                StrokeJoin::Miter(limit) => {
+                    // The miter is as long as the width divided by the cosine of half the angle
+                    let cosine =
+                        ((I::one() + dot / (half_width * half_width)) * I::cast(0.5)).sqrt();
+
+                    if cosine * limit >= I::one() && !cosine.is_zero() {
+                        let scale = (I::cast(2) * cosine * cosine).recip();
+                        Turn::Miter(((before.0 + after.0) * scale, (before.1 + after.1) * scale))
+                    } else {
+                        Turn::Bevel
+                    }
+                }
+                StrokeJoin::Round => round(),
+                StrokeJoin::Bevel => Turn::Bevel,
+            }
+        };
+
+        let mut corner = Corner {
+            vertex,
+            direction,
+            before,
+            after,
+            turn,
+            first: 0,
+            end: 0,
+        };
+
+        corner.end = corner.len();
+        Some(corner)
+    }
+
+    /// Find the nearest visit before or after `visit` that has a corner, going around the outline.
+    #[inline]
+    fn neighbour(&self, visit: usize, forwards: bool) -> Option<(usize, Corner<I>)> {
+        let mut next = visit;
+
+        for _ in 1..self.visits {
+            next = if forwards {
+                (next + 1) % self.visits
+            } else {
+                (next + self.visits - 1) % self.visits
+            };
+
+            if let Some(corner) = self.corner(next) {
+                return Some((next, corner));
+            }
+        }
+
+        None
+    }
+
+    /// Work out the outline around the inside of an inner corner, and the points that it uses.
+    #[inline]
+    fn inside(&self, visit: usize, corner: Corner<I>) -> Option<(Inside<I>, Cut)> {
+        let (_, before) = self.neighbour(visit, false)?;
+        let (_, after) = self.neighbour(visit, true)?;
+
+        let inside = Inside {
+            before,
+            corner,
+            after,
+            from: match before.turn {
+                Turn::Inner => before.len() - 1,
+                _ => 0,
+            },
+            to: match after.turn {
+                Turn::Inner => 1,
+                _ => after.len(),
+            },
+        };
+
+        Some((inside, inside.cut()?))
+    }
+
+    /// Work out the corner for a visit, and which of its points are used once the inner corners
+    /// next to it have been worked out.
+    #[inline]
+    fn placed(&self, visit: usize) -> Option<(Corner<I>, Option<Inside<I>>)> {
+        let mut corner = self.corner(visit)?;
+
+        if let Turn::Inner = corner.turn {
+            return Some(match self.inside(visit, corner) {
+                Some((inside, cut)) => {
+                    corner.first = cut.first;
+                    corner.end = cut.end;
+                    (corner, Some(inside))
+                }
+                None => (corner, None),
+            });
+        }
+
+        if let Some((before, neighbour)) = self.neighbour(visit, false) {
+            if let Turn::Inner = neighbour.turn {
+                if let Some((_, cut)) = self.inside(before, neighbour) {
+                    corner.first = cut.after;
+                }
+            }
+        }
+
+        if let Some((after, neighbour)) = self.neighbour(visit, true) {
+            if let Turn::Inner = neighbour.turn {
+                if let Some((_, cut)) = self.inside(after, neighbour) {
+                    corner.end = corner.end.min(cut.before);
+                }
+            }
+        }
+
+        Some((corner, None))
+    }
+}
+
+impl<'a, I: FloatNum> Iterator for StrokeOutline<'a, I> {
+    type Item = Point<I>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.visit < self.visits {
+            if self.corner.is_none() {
+                let placed = self.placed(self.visit);
+                self.corner = placed.map(|(corner, _)| corner);
+                self.inside = placed
+                    .and_then(|(_, inside)| inside)
+                    .map(|inside| (inside, inside.start()));
+                self.index = 0;
+            }
+
+            if let Some(corner) = self.corner {
+                while self.index < corner.end {
+                    self.index += 1;
+
+                    let point = match self.inside {
+                        Some((inside, trace)) => match inside.step(trace) {
+                            Some((point, trace)) => {
+                                self.inside = Some((inside, trace));
+                                point
+                            }
+                            None => break,
+                        },
+                        None => corner.point(self.index - 1),
+                    };
+
+                    if self.index > corner.first {
+                        return Some(point);
+                    }
+                }
+            }
+
+            self.corner = None;
+            self.visit += 1;
+        }
+
+        None
+    }
+}
+
+impl<'a, I: FloatNum> Edges<I> for StrokeOutline<'a, I> {
+    #[inline]
+    fn edges<F: FnMut(Point<I>, Point<I>)>(&self, mut edge: F) {
+        let mut points = self.clone();
+
+        if let Some(first) = points.next() {
+            let last = points.fold(first, |previous, point| {
+                edge(previous, point);
+                point
+            });
+
+            edge(last, first);
+        }
+    }
+}
+
+#[inline]
+fn add<I: FloatNum>(a: Point<I>, b: Point<I>) -> Point<I> {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+/// Get how far along the lines from `a` to `b` and from `c` to `d` they cross, if they do.
+#[inline]
+fn crossing<I: FloatNum>(a: Point<I>, b: Point<I>, c: Point<I>, d: Point<I>) -> Option<(I, I)> {
+    let (ab, cd, ac) = (sub(b, a), sub(d, c), sub(c, a));
+    let denominator = cross(ab, cd);
+
+    if denominator.is_zero() {
+        return None;
+    }
+
+    let (t, u) = (cross(ac, cd) / denominator, cross(ac, ab) / denominator);
+    let within = |value: I| value >= I::zero() && value <= I::one();
+
+    if within(t) && within(u) {
+        Some((t, u))
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn sub<I: FloatNum>(a: Point<I>, b: Point<I>) -> Point<I> {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+/// Get the cross product of `a` and `b`, which is positive when `b` is on the side of `a` that
+/// the stroke is on.
+#[inline]
+fn cross<I: FloatNum>(a: Point<I>, b: Point<I>) -> I {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Get the angle to turn clockwise from `a` to `b`, from zero to pi.
+#[inline]
+fn angle<I: FloatNum>(a: Point<I>, b: Point<I>) -> I {
+    let cross = a.0 * b.1 - a.1 * b.0;
+    let dot = a.0 * b.0 + a.1 * b.1;
+    (-cross).atan2(dot).abs()
+}
+
+#[test]
+fn tests() {
+    let vertices = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)];
+    let outline = |vertices, cap, join| {
+        StrokeOutline::<f64>::new(vertices, 2.0, cap, join, 0.1).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        outline(&vertices, StrokeCap::Butt, StrokeJoin::Bevel),
+        [
+            (0.0, -1.0),
+            (0.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 4.0),
+            (5.0, 4.0),
+            (5.0, 0.0),
+            (4.0, -1.0)
+        ]
+    );
+
+    // The inside of the corner goes around the line ends when the lines are too short to meet
+    assert_eq!(
+        outline(
+            &[(0.0, 0.0), (1.0, 0.0), (1.0, 4.0)],
+            StrokeCap::Butt,
+            StrokeJoin::Bevel
+        ),
+        [
+            (0.0, -1.0),
+            (0.0, 0.0),
+            (0.0, 4.0),
+            (2.0, 4.0),
+            (2.0, 0.0),
+            (1.0, -1.0)
+        ]
+    );
+
+    // Sharp corners are bevelled instead of mitered
+    let sharp = [(0.0, 0.0), (10.0, 0.0), (0.0, 1.0)];
+    assert_eq!(
+        outline(&sharp, StrokeCap::Butt, StrokeJoin::Miter(4.0)),
+        outline(&sharp, StrokeCap::Butt, StrokeJoin::Bevel)
+    );
+    assert!(
+        outline(&sharp, StrokeCap::Butt, StrokeJoin::Miter(100.0)).len()
+            > outline(&sharp, StrokeCap::Butt, StrokeJoin::Bevel).len()
+    );
+
+    // Vertices in the same place are skipped
+    let repeated = [(0.0, 0.0), (0.0, 0.0), (4.0, 0.0), (4.0, 0.0), (4.0, 4.0)];
+    assert_eq!(
+        outline(&repeated, StrokeCap::Square, StrokeJoin::Bevel),
+        outline(&vertices, StrokeCap::Square, StrokeJoin::Bevel)
+    );
+
+    // The anti-aliased fill covers the area of a stroke that doesn't overlap itself exactly
+    let area = |vertices, cap, join| {
+        let mut buffer = [0.0; 16];
+        StrokeOutline::<f64>::new(vertices, 2.0, cap, join, 0.1)
+            .fill_antialiased::<i32>(&mut buffer)
+            .map(|(_, value)| value)
+            .sum::<f64>()
+    };
+
+    assert!((area(&vertices, StrokeCap::Butt, StrokeJoin::Miter(4.0)) - 16.0).abs() < 1e-9);
+    assert!((area(&vertices, StrokeCap::Square, StrokeJoin::Bevel) - 19.5).abs() < 1e-9);
+    assert!((area(&[(0.5, 0.5)], StrokeCap::Square, StrokeJoin::Bevel) - 4.0).abs() < 1e-9);
+    assert_eq!(area(&[(0.5, 0.5)], StrokeCap::Butt, StrokeJoin::Bevel), 0.0);
+
+    // Pixels inside a corner with a short line are only covered once, give or take the flattening
+    // of the cap
+    let mut buffer = [0.0; 16];
+    let corner = [(0.0, 0.0), (1.0, 0.0), (5.0, 3.0f64)];
+    let value = StrokeOutline::new(&corner, 4.0, StrokeCap::Round, StrokeJoin::Round, 0.1)
+        .fill_antialiased::<i32>(&mut buffer)
+        .find(|&(point, _)| point == (0, 2))
+        .map(|(_, value)| value);
+    assert!((value.unwrap() - 0.5).abs() < 0.05);
+
+    // The aliased fill rounds the outline to whole pixels
+    let mut buffer = [(0, 0); 16];
+    assert_eq!(
+        StrokeOutline::new(&vertices, 2.0, StrokeCap::Butt, StrokeJoin::Bevel, 0.1)
+            .fill::<i32>(&mut buffer)
+            .map(|(_, length)| length)
+            .sum::<usize>(),
+        26
+    );
+}
@@ -0,0 +1,551 @@
+use {FloatNum, Path, PathCommand, Point};
+use core::f64::consts::{FRAC_PI_2, PI};
+use core::fmt;
+use core::str;
+
+/// What went wrong while parsing SVG path data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvgPathErrorKind {
+    /// A command letter was expected, but something else was found.
+    ExpectedCommand,
+    /// The path data has to start with a move to command, `M` or `m`.
+    ExpectedMoveTo,
+    /// A number was expected, but something else was found.
+    ExpectedNumber,
+    /// An arc flag was expected, which has to be either `0` or `1`.
+    ExpectedFlag,
+    /// There's no room left in the buffer for the commands.
+    PathFull,
+}
+
+/// An error from parsing SVG path data, with the byte offset into the data where it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgPathError {
+    pub kind: SvgPathErrorKind,
+    pub offset: usize,
+}
+
+impl fmt::Display for SvgPathError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self.kind {
+            SvgPathErrorKind::ExpectedCommand => "expected a command",
+            SvgPathErrorKind::ExpectedMoveTo => "expected the path to start with a move to",
+            SvgPathErrorKind::ExpectedNumber => "expected a number",
+            SvgPathErrorKind::ExpectedFlag => "expected a flag",
+            SvgPathErrorKind::PathFull => "the path is full",
+        };
+
+        write!(formatter, "{} at byte {}", message, self.offset)
+    }
+}
+
+impl<'a, I: FloatNum> Path<'a, I> {
+    /// Parse [SVG path data], like the `d` attribute of a `<path>` element, into a path that
+    /// stores its commands in `buffer`.
+    ///
+    /// All of the commands are supported, in both their absolute and relative forms. Horizontal
+    /// and vertical lines become [`LineTo`] commands, the smooth curves have their control points
+    /// reflected, and elliptical arcs are split into cubic Bézier curves of up to 90 degrees each.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// extern crate line_drawing;
+    /// use line_drawing::{LineAlgorithm, Path, PathCommand};
+    ///
+    /// fn main() {
+    ///     let mut buffer = [PathCommand::Close; 8];
+    ///     let path = Path::<f32>::from_svg("M0 0h4v3a2 2 0 0 1-4 0z", &mut buffer).unwrap();
+    ///
+    ///     for (x, y) in path.stroke::<i8>(0.25, &LineAlgorithm::Bresenham) {
+    ///         print!("({}, {}), ", x, y);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// ```text
+    /// (0, 0), (1, 0), (2, 0), (3, 0), (4, 0), (4, 1), (4, 2), (4, 3), (3, 4), (2, 5), (1, 4), (0, 3), (0, 2), (0, 1),
+    /// ```
+    ///
+    /// [SVG path data]: https://www.w3.org/TR/SVG11/paths.html#PathData
+    /// [`LineTo`]: enum.PathCommand.html#variant.LineTo
+    #[inline]
+    pub fn from_svg(data: &str, buffer: &'a mut [PathCommand<I>]) -> Result<Self, SvgPathError> {
+        let mut parser = Parser {
+            path: Path::new(buffer),
+            data: data.as_bytes(),
+            offset: 0,
+            command: None,
+            current: (I::zero(), I::zero()),
+            start: (I::zero(), I::zero()),
+            control: None,
+        };
+
+        parser.parse()?;
+        Ok(parser.path)
+    }
+}
+
+/// The kind of curve that a control point was last used for, so that the smooth curves can
+/// reflect it.
+#[derive(Clone, Copy, PartialEq)]
+enum Control {
+    Cubic,
+    Quad,
+}
+
+struct Parser<'a, 'b, I: 'a> {
+    path: Path<'a, I>,
+    data: &'b [u8],
+    offset: usize,
+    command: Option<u8>,
+    current: Point<I>,
+    start: Point<I>,
+    control: Option<(Point<I>, Control)>,
+}
+
+impl<'a, 'b, I: FloatNum> Parser<'a, 'b, I> {
+    #[inline]
+    fn parse(&mut self) -> Result<(), SvgPathError> {
+        loop {
+            self.skip_whitespace();
+
+            let offset = self.offset;
+            let byte = match self.peek() {
+                Some(byte) => byte,
+                None => return Ok(()),
+            };
+
+            let command = if byte.is_ascii_alphabetic() {
+                self.offset += 1;
+                byte
+            } else {
+                // Numbers after the arguments repeat the last command, except that a move to is
+                // followed by line tos
+                let repeat = match self.command {
+                    Some(b'Z') | Some(b'z') | None => None,
+                    Some(b'M') => Some(b'L'),
+                    Some(b'm') => Some(b'l'),
+                    command => command,
+                };
+
+                match repeat {
+                    Some(command) if is_number_start(byte) => command,
+                    _ => return Err(self.error(SvgPathErrorKind::ExpectedCommand, offset)),
+                }
+            };
+
+            if self.command.is_none() && command != b'M' && command != b'm' {
+                return Err(self.error(SvgPathErrorKind::ExpectedMoveTo, offset));
+            }
+
+            self.command = Some(command);
+            self.parse_command(command, offset)?;
+        }
+    }
+
+    /// Parse the arguments of a single command and add it to the path.
+    #[inline]
+    fn parse_command(&mut self, command: u8, offset: usize) -> Result<(), SvgPathError> {
+        let relative = command.is_ascii_lowercase();
+        let current = self.current;
+
+        let mut control = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = self.point(relative)?;
+                self.push(PathCommand::MoveTo(point), offset)?;
+                self.start = point;
+            }
+            b'L' => {
+                let point = self.point(relative)?;
+                self.push(PathCommand::LineTo(point), offset)?;
+            }
+            b'H' => {
+                let x = self.number()?;
+                let x = if relative { current.0 + x } else { x };
+                self.push(PathCommand::LineTo((x, current.1)), offset)?;
+            }
+            b'V' => {
+                let y = self.number()?;
+                let y = if relative { current.1 + y } else { y };
+                self.push(PathCommand::LineTo((current.0, y)), offset)?;
+            }
+            b'C' => {
+                let first = self.point(relative)?;
+                let second = self.point(relative)?;
+                let end = self.point(relative)?;
+                self.push(PathCommand::CubicTo(first, second, end), offset)?;
+                control = Some((second, Control::Cubic));
+            }
+            b'S' => {
+                let first = self.reflect(Control::Cubic);
+                let second = self.point(relative)?;
+                let end = self.point(relative)?;
+                self.push(PathCommand::CubicTo(first, second, end), offset)?;
+                control = Some((second, Control::Cubic));
+            }
+            b'Q' => {
+                let point = self.point(relative)?;
+                let end = self.point(relative)?;
+                self.push(PathCommand::QuadTo(point, end), offset)?;
+                control = Some((point, Control::Quad));
+            }
+            b'T' => {
+                let point = self.reflect(Control::Quad);
+                let end = self.point(relative)?;
+                self.push(PathCommand::QuadTo(point, end), offset)?;
+                control = Some((point, Control::Quad));
+            }
+            b'A' => {
+                let radii = (self.number()?, self.number()?);
+                let angle = self.number()?;
+                let large = self.flag()?;
+                let sweep = self.flag()?;
+                let end = self.point(relative)?;
+                self.arc(radii, angle, large, sweep, end, offset)?;
+            }
+            b'Z' => {
+                self.push(PathCommand::Close, offset)?;
+                self.current = self.start;
+            }
+            _ => return Err(self.error(SvgPathErrorKind::ExpectedCommand, offset)),
+        }
+
+        self.control = control;
+        Ok(())
+    }
+
+    /// Add an elliptical arc from the current point to `end` as a series of cubic curves, using
+    /// the conversion from the [SVG implementation notes].
+    ///
+    /// [SVG implementation notes]: https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes
+    #[inline]
+    fn arc(
+        &mut self,
+        radii: Point<I>,
+        angle: I,
+        large: bool,
+        sweep: bool,
+        end: Point<I>,
+        offset: usize,
+    ) -> Result<(), SvgPathError> {
+        let start = self.current;
+        let half = I::cast(0.5);
+
+        if start == end {
+            return Ok(());
+        }
+
+        let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+        if rx.is_zero() || ry.is_zero() {
+            return self.push(PathCommand::LineTo(end), offset);
+        }
+
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        // Find the endpoints relative to the middle of the chord, in the ellipse's own axes
+        let middle = ((start.0 - end.0) * half, (start.1 - end.1) * half);
+        let x = cos * middle.0 + sin * middle.1;
+        let y = cos * middle.1 - sin * middle.0;
+
+        // Scale the radii up if they're too small to reach
+        let scale = (x * x) / (rx * rx) + (y * y) / (ry * ry);
+        if scale > I::one() {
+            rx *= scale.sqrt();
+            ry *= scale.sqrt();
+        }
+
+        let numerator = rx * rx * ry * ry - rx * rx * y * y - ry * ry * x * x;
+        let denominator = rx * rx * y * y + ry * ry * x * x;
+        let mut factor = (numerator / denominator).max(I::zero()).sqrt();
+        if large == sweep {
+            factor = -factor;
+        }
+
+        let center = (factor * rx * y / ry, -factor * ry * x / rx);
+
+        let start_angle = ((y - center.1) / ry).atan2((x - center.0) / rx);
+        let end_angle = ((-y - center.1) / ry).atan2((-x - center.0) / rx);
+
+        let mut sweep_angle = end_angle - start_angle;
+        if sweep && sweep_angle < I::zero() {
+            sweep_angle += I::cast(2.0 * PI);
+        } else if !sweep && sweep_angle > I::zero() {
+            sweep_angle -= I::cast(2.0 * PI);
+        }
+
+        let center = (
+            cos * center.0 - sin * center.1 + (start.0 + end.0) * half,
+            sin * center.0 + cos * center.1 + (start.1 + end.1) * half,
+        );
+
+        // Get a point on the ellipse and the direction it's heading at an angle
+        let ellipse = |angle: I| {
+            let (a, b) = angle.sin_cos();
+            let point = (
+                center.0 + rx * b * cos - ry * a * sin,
+                center.1 + rx * b * sin + ry * a * cos,
+            );
+            let direction = (-rx * a * cos - ry * b * sin, -rx * a * sin + ry * b * cos);
+            (point, direction)
+        };
+
+        let pieces = (sweep_angle.abs() / I::cast(FRAC_PI_2))
+            .ceil()
+            .to_usize()
+            .unwrap_or(1)
+            .max(1);
+        let step = sweep_angle / I::cast(pieces);
+        let length = I::cast(4.0 / 3.0) * (step * I::cast(0.25)).tan();
+
+        let (mut from, mut from_direction) = ellipse(start_angle);
+        for piece in 1..=pieces {
+            let (to, to_direction) = if piece == pieces {
+                (end, ellipse(start_angle + sweep_angle).1)
+            } else {
+                ellipse(start_angle + step * I::cast(piece))
+            };
+
+            self.push(
+                PathCommand::CubicTo(
+                    (
+                        from.0 + from_direction.0 * length,
+                        from.1 + from_direction.1 * length,
+                    ),
+                    (
+                        to.0 - to_direction.0 * length,
+                        to.1 - to_direction.1 * length,
+                    ),
+                    to,
+                ),
+                offset,
+            )?;
+
+            from = to;
+            from_direction = to_direction;
+        }
+
+        Ok(())
+    }
+
+    /// Get the first control point for a smooth curve, which is the last control point reflected
+    /// around the current point if it was used for the same kind of curve.
+    #[inline]
+    fn reflect(&self, kind: Control) -> Point<I> {
+        match self.control {
+            Some((control, last)) if last == kind => (
+                self.current.0 + self.current.0 - control.0,
+                self.current.1 + self.current.1 - control.1,
+            ),
+            _ => self.current,
+        }
+    }
+
+    /// Add a command to the path, and move the current point to its end.
+    #[inline]
+    fn push(&mut self, command: PathCommand<I>, offset: usize) -> Result<(), SvgPathError> {
+        if self.path.is_full() {
+            return Err(self.error(SvgPathErrorKind::PathFull, offset));
+        }
+
+        match command {
+            PathCommand::MoveTo(end)
+            | PathCommand::LineTo(end)
+            | PathCommand::QuadTo(_, end)
+            | PathCommand::CubicTo(_, _, end)
+            | PathCommand::ConicTo(_, end, _) => self.current = end,
+            PathCommand::Close => {}
+        }
+
+        self.path.push(command);
+        Ok(())
+    }
+
+    /// Parse a pair of coordinates, which are offsets from the current point if `relative` is true.
+    #[inline]
+    fn point(&mut self, relative: bool) -> Result<Point<I>, SvgPathError> {
+        let x = self.number()?;
+        let y = self.number()?;
+
+        if relative {
+            Ok((self.current.0 + x, self.current.1 + y))
+        } else {
+            Ok((x, y))
+        }
+    }
+
+    /// Parse a number, and skip the whitespace and comma after it.
+    #[inline]
+    fn number(&mut self) -> Result<I, SvgPathError> {
+        self.skip_whitespace();
+
+        let start = self.offset;
+        let error = self.error(SvgPathErrorKind::ExpectedNumber, start);
+
+        if let Some(b'+') | Some(b'-') = self.peek() {
+            self.offset += 1;
+        }
+
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            digits += self.skip_digits();
+        }
+
+        if digits == 0 {
+            return Err(error);
+        }
+
+        // Only take an exponent if it has digits, so that `1e` is `1` followed by an error
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            let mantissa = self.offset;
+            self.offset += 1;
+
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.offset += 1;
+            }
+
+            if self.skip_digits() == 0 {
+                self.offset = mantissa;
+            }
+        }
+
+        let number = str::from_utf8(&self.data[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .ok_or(error)?;
+
+        self.skip_comma();
+        Ok(I::cast(number))
+    }
+
+    /// Parse an arc flag, which is a single digit that doesn't need to be separated from what
+    /// comes after it.
+    #[inline]
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_whitespace();
+
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error(SvgPathErrorKind::ExpectedFlag, self.offset)),
+        };
+
+        self.offset += 1;
+        self.skip_comma();
+        Ok(flag)
+    }
+
+    /// Skip over any digits, returning how many there were.
+    #[inline]
+    fn skip_digits(&mut self) -> usize {
+        let start = self.offset;
+        while self.peek().filter(u8::is_ascii_digit).is_some() {
+            self.offset += 1;
+        }
+
+        self.offset - start
+    }
+
+    /// Skip over whitespace, with up to one comma in it.
+    #[inline]
+    fn skip_comma(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.offset += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') | Some(b'\x0C') = self.peek()
+        {
+            self.offset += 1;
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.offset).cloned()
+    }
+
+    #[inline]
+    fn error(&self, kind: SvgPathErrorKind, offset: usize) -> SvgPathError {
+        SvgPathError { kind, offset }
+    }
+}
+
+#[inline]
+fn is_number_start(byte: u8) -> bool {
+    byte.is_ascii_digit() || byte == b'+' || byte == b'-' || byte == b'.'
+}
+
+#[test]
+fn tests() {
+    use PathCommand::*;
+
+    let mut buffer = [Close; 8];
+    let mut parse =
+        |data| Path::<f64>::from_svg(data, &mut buffer).map(|path| path.commands().to_vec());
+
+    // Relative commands, implicit line tos and numbers that run together
+    assert_eq!(
+        parse("m1 1 2-2.5.5e1,3h-1V0z l1 1"),
+        Ok(vec![
+            MoveTo((1.0, 1.0)),
+            LineTo((3.0, -1.5)),
+            LineTo((8.0, 1.5)),
+            LineTo((7.0, 1.5)),
+            LineTo((7.0, 0.0)),
+            Close,
+            LineTo((2.0, 2.0))
+        ])
+    );
+
+    // Smooth curves reflect the last control point, but only from the same kind of curve
+    assert_eq!(
+        parse("M0 0Q1 1 2 0t2 0S5 1 6 0"),
+        Ok(vec![
+            MoveTo((0.0, 0.0)),
+            QuadTo((1.0, 1.0), (2.0, 0.0)),
+            QuadTo((3.0, -1.0), (4.0, 0.0)),
+            CubicTo((4.0, 0.0), (5.0, 1.0), (6.0, 0.0))
+        ])
+    );
+
+    // A half circle becomes two quarter circles, and radii that are too small are scaled up
+    let arc = parse("M0 0A0.5 0.5 0 0 1 0 2").unwrap();
+    assert_eq!(arc.len(), 3);
+    if let CubicTo(_, _, middle) = arc[1] {
+        assert!((middle.0 - 1.0).abs() < 1e-9 && (middle.1 - 1.0).abs() < 1e-9);
+    } else {
+        panic!("expected a curve");
+    }
+    assert_eq!(
+        parse("M0 0a0 1 0 0 1 2 2"),
+        Ok(vec![MoveTo((0.0, 0.0)), LineTo((2.0, 2.0))])
+    );
+
+    let error = |kind, offset| Err(SvgPathError { kind, offset });
+    assert_eq!(parse("L1 1"), error(SvgPathErrorKind::ExpectedMoveTo, 0));
+    assert_eq!(
+        parse("M1 1 z 3"),
+        error(SvgPathErrorKind::ExpectedCommand, 7)
+    );
+    assert_eq!(
+        parse("M1,2,,3"),
+        error(SvgPathErrorKind::ExpectedCommand, 5)
+    );
+    assert_eq!(parse("M1e 2"), error(SvgPathErrorKind::ExpectedNumber, 2));
+    assert_eq!(
+        parse("M0 0a1 1 0 2 0 1 1"),
+        error(SvgPathErrorKind::ExpectedFlag, 11)
+    );
+    assert_eq!(
+        parse("M0 0 9 9 9 9 9 9 9 9 9 9 9 9 9 9 9 9"),
+        error(SvgPathErrorKind::PathFull, 33)
+    );
+}
@@ -0,0 +1,199 @@
+use {div_ceil, div_floor, isqrt, wide, Point, SignedNum};
+use octant::Octant;
+
+/// How the width of a [`ThickLine`] is measured.
+///
+/// [`ThickLine`]: struct.ThickLine.html
+pub enum WidthMeasure {
+    /// The width is measured perpendicular to the line, and the ends are square to the line.
+    /// This keeps lines of all angles looking equally thick.
+    Perpendicular,
+    /// The width is measured along the minor axis, and the ends are square to the major axis.
+    /// Every column (or row, for steep lines) has exactly `width` pixels.
+    Axis,
+}
+
+/// A line with a configurable width, in the spirit of [Murphy's algorithm].
+///
+/// The line is filled column by column (or row by row, for steep lines) along its major axis,
+/// using the same octant transform as [`Bresenham`]. A pixel is filled if its centre lies inside
+/// the line, so every pixel is returned exactly once and there are no gaps.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::{ThickLine, WidthMeasure};
+///
+/// fn main() {
+///     for (x, y) in ThickLine::new((0, 0), (4, 1), 2, &WidthMeasure::Axis) {
+///         print!("({}, {}), ", x, y);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// (0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1), (3, 0), (3, 1), (4, 1), (4, 2),
+/// ```
+///
+/// [Murphy's algorithm]: http://kt8216.unixcab.org/murphy/index.html
+/// [`Bresenham`]: struct.Bresenham.html
+pub struct ThickLine<T> {
+    octant: Octant,
+    start: Point<T>,
+    delta: Point<T>,
+    length: Option<i128>,
+    below: i128,
+    above: i128,
+    x: T,
+    end_x: T,
+    y: T,
+    end_y: T,
+}
+
+impl<T: SignedNum> ThickLine<T> {
+    #[inline]
+    pub fn new(start: Point<T>, end: Point<T>, width: T, measure: &WidthMeasure) -> Self {
+        let octant = Octant::new(start, end);
+        let start = octant.to(start);
+        let end = octant.to(end);
+
+        // A single point is drawn as if it were horizontal
+        let delta = if start == end {
+            (T::one(), T::zero())
+        } else {
+            (end.0 - start.0, end.1 - start.1)
+        };
+
+        // Each pixel is checked against the distance to the line, scaled up by `delta.0` for the
+        // axis or by the length of `delta` for the perpendicular, and must lie in `-below..=above`.
+        // These scaled distances can overflow `T` even for short lines, so they're kept in `i128`.
+        let (wide_width, dx, dy) = (wide(width), wide(delta.0), wide(delta.1));
+        let (below, above, length, first_x) = match *measure {
+            WidthMeasure::Perpendicular => {
+                let squared = wide_width * wide_width * (dx * dx + dy * dy);
+                let scaled = isqrt(squared);
+                let above = scaled / 2;
+                let below = if scaled * scaled == squared && scaled % 2 == 0 {
+                    above - 1
+                } else {
+                    above
+                };
+
+                // The corners can stick out past the ends by up to half the width
+                let length = wide(end.0 - start.0) * dx + wide(end.1 - start.1) * dy;
+                (below, above, Some(length), -(width / T::cast(2)) - T::one())
+            }
+            WidthMeasure::Axis => {
+                let above = wide_width * dx / 2;
+                let below = (wide_width * dx + 1) / 2 - 1;
+                (below, above, None, T::zero())
+            }
+        };
+
+        let end_x = end.0 - start.0 - first_x;
+
+        Self {
+            octant,
+            start,
+            delta,
+            length,
+            below,
+            above,
+            x: first_x - T::one(),
+            end_x,
+            y: T::one(),
+            end_y: T::zero(),
+        }
+    }
+
+    /// Get the range of pixels to fill in the column `x` along from the start.
+    #[inline]
+    fn column(&self, x: T) -> (T, T) {
+        let (dx, dy, x) = (wide(self.delta.0), wide(self.delta.1), wide(x));
+
+        let mut low = div_ceil(dy * x - self.below, dx);
+        let mut high = div_floor(dy * x + self.above, dx);
+
+        // Cut off the ends of the line square to its direction
+        if let Some(length) = self.length {
+            if dy == 0 {
+                if dx * x < 0 || dx * x > length {
+                    return (T::one(), T::zero());
+                }
+            } else {
+                low = low.max(div_ceil(-dx * x, dy));
+                high = high.min(div_floor(length - dx * x, dy));
+            }
+        }
+
+        // The cut can put an empty column's range out of reach of `T`
+        if low > high {
+            (T::one(), T::zero())
+        } else {
+            (T::cast(low), T::cast(high))
+        }
+    }
+}
+
+impl<T: SignedNum> Iterator for ThickLine<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.y > self.end_y {
+            if self.x >= self.end_x {
+                return None;
+            }
+
+            self.x += T::one();
+            let (low, high) = self.column(self.x);
+            self.y = low;
+            self.end_y = high;
+        }
+
+        let point = (self.start.0 + self.x, self.start.1 + self.y);
+        self.y += T::one();
+
+        Some(self.octant.from(point))
+    }
+}
+
+#[test]
+fn tests() {
+    use WidthMeasure::{Axis, Perpendicular};
+
+    let thick = |a, b, width, measure| ThickLine::new(a, b, width, measure).collect::<Vec<_>>();
+
+    assert_eq!(
+        thick((0, 0), (3, 0), 3, &Perpendicular),
+        [
+            (0, -1),
+            (0, 0),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (2, -1),
+            (2, 0),
+            (2, 1),
+            (3, -1),
+            (3, 0),
+            (3, 1)
+        ]
+    );
+
+    // Even widths lean towards the positive side of the line
+    assert_eq!(
+        thick((0, 0), (0, -2), 2, &Axis),
+        [(0, 0), (-1, 0), (0, -1), (-1, -1), (0, -2), (-1, -2)]
+    );
+
+    assert_eq!(thick((2, 2), (2, 2), 1, &Perpendicular), [(2, 2)]);
+    assert_eq!(thick((2, 2), (2, 2), 2, &Axis), [(2, 2), (2, 3)]);
+    assert_eq!(thick((0, 0), (5, 5), 0, &Axis), []);
+
+    // Diagonal lines are wider along each column when measured perpendicularly
+    assert_eq!(ThickLine::new((0, 0), (10, 10), 3, &Axis).count(), 33);
+    assert!(ThickLine::new((0, 0), (10, 10), 3, &Perpendicular).count() > 33);
+}
@@ -1,4 +1,4 @@
-use {FloatNum, SignedNum, Voxel};
+use {isqrt, FloatNum, SignedNum, Voxel};
 use steps::Steps;
 
 #[inline]
@@ -91,6 +91,11 @@ pub struct WalkVoxels<I, O> {
     d_err_x: I,
     d_err_y: I,
     d_err_z: I,
+    total: O,
+    taken_back: O,
+    back_index: O,
+    back: Voxel<O>,
+    back_err: Voxel<I>,
 }
 
 impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
@@ -146,22 +151,31 @@ impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
         let vxvz = vx * vz;
         let vyvz = vy * vz;
 
+        // Error from the next plane accumulators, scaled up by vx * vy * vz
+        // gx0 + vx * rx === gxp
+        // vx * rx === gxp - gx0
+        // rx === (gxp - gx0) / vx
+        let err_x = (I::cast(x_plane) - start.0) * vyvz;
+        let err_y = (I::cast(y_plane) - start.1) * vxvz;
+        let err_z = (I::cast(z_plane) - start.2) * vxvy;
+
         Self {
             sign_x,
             sign_y,
             sign_z,
             count,
             voxel: start_i,
-            // Error from the next plane accumulators, scaled up by vx * vy * vz
-            // gx0 + vx * rx === gxp
-            // vx * rx === gxp - gx0
-            // rx === (gxp - gx0) / vx
-            err_x: (I::cast(x_plane) - start.0) * vyvz,
-            err_y: (I::cast(y_plane) - start.1) * vxvz,
-            err_z: (I::cast(z_plane) - start.2) * vxvy,
+            err_x,
+            err_y,
+            err_z,
             d_err_x: I::cast(sign_x) * vyvz,
             d_err_y: I::cast(sign_y) * vxvz,
             d_err_z: I::cast(sign_z) * vxvy,
+            total: count,
+            taken_back: O::zero(),
+            back_index: O::zero(),
+            back: start_i,
+            back_err: (err_x, err_y, err_z),
         }
     }
 
@@ -169,6 +183,24 @@ impl<I: FloatNum, O: SignedNum> WalkVoxels<I, O> {
     pub fn steps(self) -> Steps<Voxel<O>, Self> {
         Steps::new(self)
     }
+
+    /// Take `steps` steps on from `voxel` with the error terms `err`, in the same way as `next`,
+    /// and get the voxel and error terms that the walk ends up at.
+    #[inline]
+    fn walk(&self, voxel: Voxel<O>, err: Voxel<I>, steps: O) -> (Voxel<O>, Voxel<I>) {
+        let mut walk = Self {
+            voxel,
+            err_x: err.0,
+            err_y: err.1,
+            err_z: err.2,
+            count: steps - O::one(),
+            ..*self
+        };
+
+        while walk.next().is_some() {}
+
+        (walk.voxel, (walk.err_x, walk.err_y, walk.err_z))
+    }
 }
 
 impl<I: FloatNum, O: SignedNum> Iterator for WalkVoxels<I, O> {
@@ -206,8 +238,59 @@ impl<I: FloatNum, O: SignedNum> Iterator for WalkVoxels<I, O> {
             None
         }
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.count + O::one()).max(O::zero()).to_usize().unwrap();
+        (len, Some(len))
+    }
 }
 
+/// The error terms are added up one step at a time, and rounding means they can't be worked out
+/// directly for a voxel further along without choosing different voxels. So each voxel from the
+/// back is found by walking forwards to it from a little way behind it.
+impl<I: FloatNum, O: SignedNum> DoubleEndedIterator for WalkVoxels<I, O> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count >= O::zero() {
+            // The indices of the voxels at the back and the front, counted from the start
+            let index = self.total - self.taken_back;
+            let front = index - self.count;
+
+            // Once the back has passed the voxel it's walked to from, or left it too far behind,
+            // move it to about the square root of the remaining length before the back, so
+            // reversing the whole walk doesn't take time proportional to its square
+            let gap = isqrt(self.count);
+
+            if self.back_index > index || index - self.back_index > gap * O::cast(2) {
+                let target = index - gap;
+
+                let (back, back_err) = if front <= self.back_index && self.back_index < target {
+                    self.walk(self.back, self.back_err, target - self.back_index)
+                } else {
+                    let err = (self.err_x, self.err_y, self.err_z);
+                    self.walk(self.voxel, err, target - front)
+                };
+
+                self.back_index = target;
+                self.back = back;
+                self.back_err = back_err;
+            }
+
+            let (voxel, _) = self.walk(self.back, self.back_err, index - self.back_index);
+
+            self.count -= O::one();
+            self.taken_back += O::one();
+
+            Some(voxel)
+        } else {
+            None
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> ExactSizeIterator for WalkVoxels<I, O> {}
+
 #[test]
 fn tests() {
     assert_eq!(
@@ -2,12 +2,33 @@ use {FloatNum, Point, SignedNum};
 use steps::Steps;
 use core::mem::swap;
 
+/// How a [`XiaolinWu`] line handles its ends.
+///
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoints {
+    /// Each end only covers as much of its column as the line actually reaches, so lines can
+    /// start and end part of the way through a pixel. Points are returned from the start of the
+    /// line to the end.
+    Fractional,
+    /// Both ends are rounded to the nearest column and covered fully, and points are always
+    /// returned from left to right (or top to bottom, for steep lines). This is how `XiaolinWu`
+    /// used to work before [`Fractional`] was added.
+    ///
+    /// [`Fractional`]: #variant.Fractional
+    Rounded,
+}
+
 /// An implementation of [Xiaolin Wu's line algorithm].
 ///
 /// This algorithm works based on floating-points and returns an extra variable for how much a
 /// a point is covered, which is useful for anti-aliasing.
 ///
-/// Note that due to the implementation, the returned line will always go from left to right.
+/// The coordinates are the centres of pixels. By default the ends of the line use
+/// [`Endpoints::Fractional`], so a line that starts or ends on the centre of a pixel only covers
+/// half of it. This means that lines joined end to end add up to full coverage where they meet,
+/// instead of leaving a bright dot at every corner. Use [`with_endpoints`] to pick
+/// [`Endpoints::Rounded`] instead.
 ///
 /// Example:
 ///
@@ -23,22 +44,34 @@ use core::mem::swap;
 /// ```
 ///
 /// ```text
-/// ((0, 0), 0.5), ((0, 1), 0.5), ((1, 1), 0.5), ((1, 2), 1), ((1, 3), 0.5), ((2, 3), 0.5), ((2, 4), 1), ((2, 5), 0.5), ((3, 5), 0.5), ((3, 6), 0.5),
+/// ((0, 0), 0.5), ((0, 1), 0.5), ((1, 1), 0.5), ((1, 2), 1), ((1, 3), 0.5), ((2, 3), 0.5), ((2, 4), 1),
+/// ((2, 5), 0.5), ((3, 5), 0.5), ((3, 6), 0.5),
 /// ```
 ///
 /// [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+/// [`Endpoints::Fractional`]: enum.Endpoints.html#variant.Fractional
+/// [`Endpoints::Rounded`]: enum.Endpoints.html#variant.Rounded
+/// [`with_endpoints`]: #method.with_endpoints
 pub struct XiaolinWu<I, O> {
     steep: bool,
+    step: O,
     gradient: I,
     x: O,
     y: I,
     end_x: O,
+    coverage: I,
+    end_coverage: I,
     lower: bool,
 }
 
 impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
     #[inline]
-    pub fn new(mut start: Point<I>, mut end: Point<I>) -> Self {
+    pub fn new(start: Point<I>, end: Point<I>) -> Self {
+        Self::with_endpoints(start, end, Endpoints::Fractional)
+    }
+
+    #[inline]
+    pub fn with_endpoints(mut start: Point<I>, mut end: Point<I>, endpoints: Endpoints) -> Self {
         let steep = (end.1 - start.1).abs() > (end.0 - start.0).abs();
 
         if steep {
@@ -46,31 +79,100 @@ impl<I: FloatNum, O: SignedNum> XiaolinWu<I, O> {
             end = (end.1, end.0);
         }
 
+        // Walk right to left by flipping the major axis, and flipping it back when returning points
+        let step = if endpoints == Endpoints::Fractional && start.0 > end.0 {
+            start.0 = -start.0;
+            end.0 = -end.0;
+            -O::one()
+        } else {
+            O::one()
+        };
+
         if start.0 > end.0 {
             swap(&mut start, &mut end);
         }
 
         let dx = end.0 - start.0;
-        let gradient = if dx.is_zero() {
-            I::one()
-        } else {
-            (end.1 - start.1) / dx
-        };
 
-        Self {
-            steep,
-            gradient,
-            x: O::cast(start.0.round()),
-            y: start.1,
-            end_x: O::cast(end.0.round()),
-            lower: false,
+        match endpoints {
+            Endpoints::Fractional => {
+                let half = I::cast(0.5);
+                let gradient = if dx.is_zero() {
+                    I::zero()
+                } else {
+                    (end.1 - start.1) / dx
+                };
+
+                // Each column covers `x - 0.5..x + 0.5`
+                let x = (start.0 + half).floor();
+                let end_x = (end.0 - half).ceil();
+
+                let (end_x, coverage, end_coverage) = if end_x > x {
+                    (end_x, x + half - start.0, end.0 + half - end_x)
+                } else {
+                    (x, dx, dx)
+                };
+
+                Self {
+                    steep,
+                    step,
+                    gradient,
+                    x: O::cast(x),
+                    y: start.1 + (x - start.0) * gradient,
+                    end_x: O::cast(end_x),
+                    coverage,
+                    end_coverage,
+                    lower: false,
+                }
+            }
+            Endpoints::Rounded => Self {
+                steep,
+                step,
+                gradient: if dx.is_zero() {
+                    I::one()
+                } else {
+                    (end.1 - start.1) / dx
+                },
+                x: O::cast(start.0.round()),
+                y: start.1,
+                end_x: O::cast(end.0.round()),
+                coverage: I::one(),
+                end_coverage: I::one(),
+                lower: false,
+            },
         }
     }
 
+    /// Get the position along the major axis of the next point, along with the centre of the
+    /// line there.
+    #[inline]
+    pub(crate) fn position(&self) -> (O, Point<I>) {
+        let x = self.x * self.step;
+        let centre = if self.steep {
+            (self.y, I::cast(x))
+        } else {
+            (I::cast(x), self.y)
+        };
+        (x, centre)
+    }
+
     #[inline]
     pub fn steps(self) -> Steps<(Point<O>, I), Self> {
         Steps::new(self)
     }
+
+    /// Move on to the next column.
+    #[inline]
+    fn advance(&mut self) {
+        self.x += O::one();
+        self.y += self.gradient;
+
+        self.coverage = if self.x == self.end_x {
+            self.end_coverage
+        } else {
+            I::one()
+        };
+    }
 }
 
 impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
@@ -80,35 +182,36 @@ impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
     fn next(&mut self) -> Option<Self::Item> {
         if self.x <= self.end_x {
             // get the fractional part of y
-            let fpart = self.y - self.y.floor();
+            let floor = self.y.floor();
+            let fpart = self.y - floor;
 
             // Calculate the integer value of y
-            let mut y = O::cast(self.y);
+            let mut y = O::cast(floor);
             if self.lower {
                 y += O::one();
             }
 
             // Get the point
-            let point = if self.steep { (y, self.x) } else { (self.x, y) };
+            let x = self.x * self.step;
+            let point = if self.steep { (y, x) } else { (x, y) };
+            let coverage = self.coverage;
 
             if self.lower {
                 // Return the lower point
                 self.lower = false;
-                self.x += O::one();
-                self.y += self.gradient;
-                Some((point, fpart))
+                self.advance();
+                Some((point, fpart * coverage))
             } else {
                 if fpart > I::zero() {
                     // Set to return the lower point if the fractional part is > 0
                     self.lower = true;
                 } else {
                     // Otherwise move on
-                    self.x += O::one();
-                    self.y += self.gradient;
+                    self.advance();
                 }
 
                 // Return the remainer of the fractional part
-                Some((point, I::one() - fpart))
+                Some((point, (I::one() - fpart) * coverage))
             }
         } else {
             None
@@ -118,7 +221,7 @@ impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWu<I, O> {
 
 #[test]
 fn tests() {
-    let xiaolin_wu = |a, b| XiaolinWu::new(a, b).collect::<Vec<_>>();
+    let xiaolin_wu = |a, b| XiaolinWu::with_endpoints(a, b, Endpoints::Rounded).collect::<Vec<_>>();
 
     assert_eq!(
         xiaolin_wu((0.0, 0.0), (6.0, 3.0)),
@@ -164,4 +267,25 @@ fn tests() {
         xiaolin_wu((340.5, 290.77), (110.0, 170.0)),
         xiaolin_wu((110.0, 170.0), (340.5, 290.77))
     );
+
+    let xiaolin_wu = |a, b| XiaolinWu::<f64, i32>::new(a, b).collect::<Vec<_>>();
+
+    // Ends on the centre of a pixel only cover half of it
+    assert_eq!(
+        xiaolin_wu((0.0, 0.0), (3.0, 0.0)),
+        [((0, 0), 0.5), ((1, 0), 1.0), ((2, 0), 1.0), ((3, 0), 0.5)]
+    );
+
+    // Lines are returned from start to end
+    assert_eq!(
+        xiaolin_wu((3.0, 0.0), (1.25, 0.0)),
+        [((3, 0), 0.5), ((2, 0), 1.0), ((1, 0), 0.25)]
+    );
+
+    assert_eq!(
+        xiaolin_wu((0.0, 2.5), (0.0, 0.5)),
+        [((0, 2), 1.0), ((0, 1), 1.0)]
+    );
+
+    assert_eq!(xiaolin_wu((0.75, 0.0), (1.0, 0.0)), [((1, 0), 0.25)]);
 }
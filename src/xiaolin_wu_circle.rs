@@ -0,0 +1,176 @@
+use {FloatNum, Point, SignedNum};
+
+/// An anti-aliased circle, in the style of [Xiaolin Wu's line algorithm].
+///
+/// Like [`XiaolinWu`], this returns an extra variable for how much a point is covered. The circle
+/// is split into four sides around its centre: the sides above and below it are walked column by
+/// column, and the sides to the left and right row by row. Each step returns the pixels just inside and
+/// just outside the circle, with coverage based on how far they are from it along the step.
+///
+/// Every pixel belongs to exactly one side, depending on which way it lies from the centre, so no
+/// pixel is returned twice. Where two sides meet at 45 degrees both ways of measuring the distance
+/// agree, so the coverage changes smoothly all the way around the circle.
+///
+/// The points are returned one side at a time, starting with the side with the largest y values
+/// and then going around through the largest x, smallest y and smallest x. Each side is walked in
+/// order of increasing x or y.
+///
+/// Example:
+///
+/// ```
+/// extern crate line_drawing;
+/// use line_drawing::XiaolinWuCircle;
+///
+/// fn main() {
+///     for ((x, y), value) in XiaolinWuCircle::<f32, i8>::new(0.0, 0.0, 1.5) {
+///         print!("(({}, {}), {:.2}), ", x, y, value);
+///     }
+/// }
+/// ```
+///
+/// ```text
+/// ((-1, 1), 0.88), ((-1, 2), 0.12), ((0, 1), 0.50), ((0, 2), 0.50), ((1, 1), 0.88), ((1, 2), 0.12), ((2, -1), 0.12), ((1, 0), 0.50), ((2, 0), 0.50), ((2, 1), 0.12), ((-1, -2), 0.12), ((-1, -1), 0.88), ((0, -2), 0.50), ((0, -1), 0.50), ((1, -2), 0.12), ((1, -1), 0.88), ((-2, -1), 0.12), ((-2, 0), 0.50), ((-1, 0), 0.50), ((-2, 1), 0.12),
+/// ```
+///
+/// [Xiaolin Wu's line algorithm]: https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+/// [`XiaolinWu`]: struct.XiaolinWu.html
+pub struct XiaolinWuCircle<I, O> {
+    center: Point<I>,
+    radius: I,
+    side: u8,
+    position: O,
+    end: O,
+    next: Option<(Point<O>, I)>,
+}
+
+impl<I: FloatNum, O: SignedNum> XiaolinWuCircle<I, O> {
+    #[inline]
+    pub fn new(center_x: I, center_y: I, radius: I) -> Self {
+        let mut circle = Self {
+            center: (center_x, center_y),
+            radius,
+            side: 0,
+            position: O::one(),
+            end: O::zero(),
+            next: None,
+        };
+
+        circle.start_side();
+        circle
+    }
+
+    /// Find the columns or rows that the current side is walked along.
+    #[inline]
+    fn start_side(&mut self) {
+        let center = self.flip(self.center).0;
+
+        if self.radius >= I::zero() {
+            self.position = O::cast((center - self.radius).ceil());
+            self.end = O::cast((center + self.radius).floor());
+        }
+    }
+
+    /// Swap the axes for the left and right sides, so each step is always along x.
+    #[inline]
+    fn flip<T>(&self, point: Point<T>) -> Point<T> {
+        if self.side & 1 == 0 {
+            point
+        } else {
+            (point.1, point.0)
+        }
+    }
+
+    /// Get the pixel at `x` and `y` with its coverage, where the axes are flipped for the current
+    /// side, unless it's empty or belongs to a different side.
+    #[inline]
+    fn pixel(&self, x: O, y: I, coverage: I) -> Option<(Point<O>, I)> {
+        let center = self.flip(self.center);
+        let offset = self.flip((I::cast(x) - center.0, y - center.1));
+
+        if coverage > I::zero() && self.owns(offset) {
+            Some((self.flip((x, O::cast(y))), coverage))
+        } else {
+            None
+        }
+    }
+
+    /// Check if the pixel `offset` from the centre is drawn by the current side.
+    #[inline]
+    fn owns(&self, offset: Point<I>) -> bool {
+        let (x, y) = (offset.0.abs(), offset.1.abs());
+
+        match self.side {
+            0 => x <= y && offset.1 >= I::zero(),
+            1 => x > y && offset.0 > I::zero(),
+            2 => x <= y && offset.1 < I::zero(),
+            _ => x > y && offset.0 < I::zero(),
+        }
+    }
+}
+
+impl<I: FloatNum, O: SignedNum> Iterator for XiaolinWuCircle<I, O> {
+    type Item = (Point<O>, I);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.next.take() {
+            return Some(item);
+        }
+
+        while self.side < 4 {
+            if self.position > self.end {
+                self.side += 1;
+                self.start_side();
+                continue;
+            }
+
+            let position = self.position;
+            self.position += O::one();
+
+            let center = self.flip(self.center);
+            let x = I::cast(position) - center.0;
+            let distance = (self.radius * self.radius - x * x).max(I::zero()).sqrt();
+            let y = if self.side < 2 {
+                center.1 + distance
+            } else {
+                center.1 - distance
+            };
+
+            let floor = y.floor();
+            let fpart = y - floor;
+
+            // Return the pixels on either side of the circle that belong to this side
+            let low = self.pixel(position, floor, I::one() - fpart);
+            let high = self.pixel(position, floor + I::one(), fpart);
+
+            match (low, high) {
+                (Some(low), high) => {
+                    self.next = high;
+                    return Some(low);
+                }
+                (None, Some(high)) => return Some(high),
+                (None, None) => continue,
+            }
+        }
+
+        None
+    }
+}
+
+#[test]
+fn tests() {
+    let circle = |x, y, radius| XiaolinWuCircle::<f64, i32>::new(x, y, radius).collect::<Vec<_>>();
+
+    assert_eq!(
+        circle(0.0, 0.0, 1.0),
+        [((0, 1), 1.0), ((1, 0), 1.0), ((0, -1), 1.0), ((-1, 0), 1.0)]
+    );
+
+    // Points on the diagonals are only returned once
+    let points = circle(0.5, 0.5, 5.0);
+    for (i, a) in points.iter().enumerate() {
+        assert!(points[i + 1..].iter().all(|b| a.0 != b.0));
+    }
+
+    assert_eq!(circle(0.0, 0.0, -1.0), []);
+}